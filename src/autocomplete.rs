@@ -60,14 +60,31 @@ impl TagSuggestion {
     /// Returns the category name based on the category ID.
     #[must_use]
     pub fn category_name(&self) -> Option<&'static str> {
-        self.category.map(|c| match c {
-            0 => "general",
-            1 => "artist",
-            3 => "copyright",
-            4 => "character",
-            5 => "meta",
-            _ => "unknown",
-        })
+        self.category.map(category_name)
+    }
+}
+
+/// Returns the name of a Danbooru-style tag category ID.
+///
+/// Unknown IDs map to `"unknown"`.
+///
+/// # Example
+///
+/// ```
+/// use booru_rs::autocomplete::category_name;
+///
+/// assert_eq!(category_name(4), "character");
+/// assert_eq!(category_name(42), "unknown");
+/// ```
+#[must_use]
+pub fn category_name(category: u8) -> &'static str {
+    match category {
+        0 => "general",
+        1 => "artist",
+        3 => "copyright",
+        4 => "character",
+        5 => "meta",
+        _ => "unknown",
     }
 }
 
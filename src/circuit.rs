@@ -0,0 +1,511 @@
+//! Circuit breaking for booru hosts.
+//!
+//! When a site goes down, retrying every request only adds load to a
+//! struggling server and keeps workers busy waiting for timeouts. A
+//! [`CircuitBreaker`] tracks the outcome of recent requests per host and,
+//! once the failure rate crosses a threshold, *opens* the circuit: further
+//! requests to that host fail immediately with
+//! [`BooruError::CircuitOpen`] until a cool-down period has passed.
+//!
+//! After the cool-down the circuit becomes *half-open* and lets a limited
+//! number of trial requests through. A successful trial closes the circuit
+//! again, a failed one re-opens it.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use booru_rs::circuit::{CircuitBreaker, CircuitBreakerConfig};
+//! use booru_rs::prelude::*;
+//! use std::time::Duration;
+//!
+//! # async fn example() -> Result<()> {
+//! let breaker = CircuitBreaker::with_config(CircuitBreakerConfig {
+//!     failure_rate_threshold: 0.5,
+//!     cool_down: Duration::from_secs(60),
+//!     ..Default::default()
+//! })
+//! .on_state_change(|event| {
+//!     eprintln!("{}: {:?} -> {:?}", event.host, event.from, event.to);
+//! });
+//!
+//! // Share the breaker between clients and the downloader
+//! let client = SafebooruClient::builder()
+//!     .circuit_breaker(breaker.clone())
+//!     .tag("landscape")?
+//!     .build();
+//! let downloader = Downloader::new().with_circuit_breaker(breaker);
+//!
+//! match client.get().await {
+//!     Err(BooruError::CircuitOpen { host, retry_after }) => {
+//!         println!("{host} is down, retry in {retry_after:?}");
+//!     }
+//!     result => println!("{} posts", result?.len()),
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use reqwest::{StatusCode, Url};
+
+use crate::error::{BooruError, Result};
+
+/// Minimum time after which an unfinished half-open trial is abandoned.
+const TRIAL_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The state of a circuit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CircuitState {
+    /// Requests flow normally and outcomes are recorded.
+    Closed,
+    /// Requests fail immediately until the cool-down has passed.
+    Open,
+    /// A limited number of trial requests are let through.
+    HalfOpen,
+}
+
+/// Configuration for a [`CircuitBreaker`].
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Fraction of failed requests (`0.0..=1.0`) in the window that opens the circuit.
+    pub failure_rate_threshold: f64,
+    /// Minimum number of requests in the window before the failure rate is evaluated.
+    pub minimum_requests: usize,
+    /// Number of most recent requests considered when computing the failure rate.
+    pub window_size: usize,
+    /// How long the circuit stays open before allowing trial requests.
+    pub cool_down: Duration,
+    /// Number of concurrent trial requests allowed while half-open.
+    pub half_open_max_requests: u32,
+}
+
+impl Default for CircuitBreakerConfig {
+    /// Default configuration: open at 50% failures over the last 20 requests
+    /// (at least 10), with a 30 second cool-down.
+    fn default() -> Self {
+        Self {
+            failure_rate_threshold: 0.5,
+            minimum_requests: 10,
+            window_size: 20,
+            cool_down: Duration::from_secs(30),
+            half_open_max_requests: 1,
+        }
+    }
+}
+
+/// A state transition of a host's circuit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitEvent {
+    /// The host whose circuit changed state.
+    pub host: String,
+    /// The previous state.
+    pub from: CircuitState,
+    /// The new state.
+    pub to: CircuitState,
+}
+
+/// A callback type for circuit state changes.
+pub type CircuitCallback = Arc<dyn Fn(&CircuitEvent) + Send + Sync>;
+
+/// Per-host circuit bookkeeping.
+#[derive(Debug)]
+struct HostCircuit {
+    state: CircuitState,
+    /// Recent outcomes, `true` for failures.
+    outcomes: VecDeque<bool>,
+    opened_at: Instant,
+    trials_in_flight: u32,
+    /// When the most recent trial request was let through.
+    trial_started_at: Instant,
+}
+
+impl HostCircuit {
+    fn new() -> Self {
+        Self {
+            state: CircuitState::Closed,
+            outcomes: VecDeque::new(),
+            opened_at: Instant::now(),
+            trials_in_flight: 0,
+            trial_started_at: Instant::now(),
+        }
+    }
+
+    fn failure_rate(&self) -> f64 {
+        let failures = self.outcomes.iter().filter(|&&failed| failed).count();
+        failures as f64 / self.outcomes.len() as f64
+    }
+}
+
+/// A circuit breaker keyed by host.
+///
+/// The breaker is `Clone` and shares its state, so a single instance can be
+/// passed to several clients and a [`Downloader`] to protect every request
+/// made to a host.
+///
+/// [`Downloader`]: crate::download::Downloader
+#[derive(Clone)]
+pub struct CircuitBreaker {
+    hosts: Arc<Mutex<HashMap<String, HostCircuit>>>,
+    config: CircuitBreakerConfig,
+    on_state_change: Option<CircuitCallback>,
+}
+
+impl CircuitBreaker {
+    /// Creates a circuit breaker with default configuration.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_config(CircuitBreakerConfig::default())
+    }
+
+    /// Creates a circuit breaker with the given configuration.
+    #[must_use]
+    pub fn with_config(config: CircuitBreakerConfig) -> Self {
+        Self {
+            hosts: Arc::new(Mutex::new(HashMap::new())),
+            config,
+            on_state_change: None,
+        }
+    }
+
+    /// Sets a hook called whenever a host's circuit changes state.
+    #[must_use]
+    pub fn on_state_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&CircuitEvent) + Send + Sync + 'static,
+    {
+        self.on_state_change = Some(Arc::new(callback));
+        self
+    }
+
+    /// Returns the current state of a host's circuit.
+    ///
+    /// Hosts that have never been seen are [`CircuitState::Closed`]. An open
+    /// circuit whose cool-down has passed is reported as
+    /// [`CircuitState::HalfOpen`].
+    #[must_use]
+    pub fn state(&self, host: &str) -> CircuitState {
+        let hosts = self.lock();
+        match hosts.get(host) {
+            Some(circuit)
+                if circuit.state == CircuitState::Open
+                    && circuit.opened_at.elapsed() >= self.config.cool_down =>
+            {
+                CircuitState::HalfOpen
+            }
+            Some(circuit) => circuit.state,
+            None => CircuitState::Closed,
+        }
+    }
+
+    /// Checks whether a request to `host` may proceed.
+    ///
+    /// Every successful check must be followed by a call to
+    /// [`record_success`](Self::record_success) or
+    /// [`record_failure`](Self::record_failure).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::CircuitOpen`] if the circuit is open, or if it
+    /// is half-open and all trial slots are taken.
+    pub fn check(&self, host: &str) -> Result<()> {
+        let mut event = None;
+        let result = {
+            let mut hosts = self.lock();
+            let circuit = hosts
+                .entry(host.to_string())
+                .or_insert_with(HostCircuit::new);
+
+            if circuit.state == CircuitState::Open {
+                let elapsed = circuit.opened_at.elapsed();
+                if elapsed < self.config.cool_down {
+                    return Err(BooruError::CircuitOpen {
+                        host: host.to_string(),
+                        retry_after: self.config.cool_down - elapsed,
+                    });
+                }
+                event = Some(self.transition(host, circuit, CircuitState::HalfOpen));
+            }
+
+            if circuit.state == CircuitState::HalfOpen {
+                // Trials whose outcome was never recorded (e.g. a cancelled
+                // request) must not keep the circuit half-open forever.
+                if circuit.trial_started_at.elapsed() >= self.config.cool_down.max(TRIAL_TIMEOUT) {
+                    circuit.trials_in_flight = 0;
+                }
+
+                if circuit.trials_in_flight >= self.config.half_open_max_requests {
+                    Err(BooruError::CircuitOpen {
+                        host: host.to_string(),
+                        retry_after: Duration::ZERO,
+                    })
+                } else {
+                    circuit.trials_in_flight += 1;
+                    circuit.trial_started_at = Instant::now();
+                    Ok(())
+                }
+            } else {
+                Ok(())
+            }
+        };
+
+        self.emit(event);
+        result
+    }
+
+    /// Records a successful request to `host`.
+    pub fn record_success(&self, host: &str) {
+        self.record(host, false);
+    }
+
+    /// Records a failed request to `host`.
+    pub fn record_failure(&self, host: &str) {
+        self.record(host, true);
+    }
+
+    /// Resets a host's circuit to [`CircuitState::Closed`].
+    pub fn reset(&self, host: &str) {
+        let event = {
+            let mut hosts = self.lock();
+            hosts.remove(host).and_then(|circuit| {
+                (circuit.state != CircuitState::Closed).then(|| CircuitEvent {
+                    host: host.to_string(),
+                    from: circuit.state,
+                    to: CircuitState::Closed,
+                })
+            })
+        };
+        self.emit(event);
+    }
+
+    /// Records the outcome of a request that passed [`check`](Self::check).
+    ///
+    /// Transport failures, server errors and HTTP 429 count as failures; any
+    /// other response means the host is up.
+    pub(crate) fn record_outcome(
+        &self,
+        host: &str,
+        outcome: &std::result::Result<reqwest::Response, reqwest::Error>,
+    ) {
+        let failed = match outcome {
+            Ok(response) => is_failure_status(response.status()),
+            Err(e) => e.is_timeout() || e.is_connect() || e.is_request(),
+        };
+        self.record(host, failed);
+    }
+
+    fn record(&self, host: &str, failed: bool) {
+        let event = {
+            let mut hosts = self.lock();
+            let circuit = hosts
+                .entry(host.to_string())
+                .or_insert_with(HostCircuit::new);
+
+            match circuit.state {
+                CircuitState::HalfOpen => {
+                    circuit.trials_in_flight = circuit.trials_in_flight.saturating_sub(1);
+                    circuit.outcomes.clear();
+                    if failed {
+                        Some(self.transition(host, circuit, CircuitState::Open))
+                    } else {
+                        Some(self.transition(host, circuit, CircuitState::Closed))
+                    }
+                }
+                // Late results from requests started before the circuit opened
+                CircuitState::Open => None,
+                CircuitState::Closed => {
+                    circuit.outcomes.push_back(failed);
+                    while circuit.outcomes.len() > self.config.window_size.max(1) {
+                        circuit.outcomes.pop_front();
+                    }
+
+                    let tripped = failed
+                        && circuit.outcomes.len() >= self.config.minimum_requests.max(1)
+                        && circuit.failure_rate() >= self.config.failure_rate_threshold;
+
+                    tripped.then(|| self.transition(host, circuit, CircuitState::Open))
+                }
+            }
+        };
+        self.emit(event);
+    }
+
+    fn transition(&self, host: &str, circuit: &mut HostCircuit, to: CircuitState) -> CircuitEvent {
+        let from = circuit.state;
+        circuit.state = to;
+        if to == CircuitState::Open {
+            circuit.opened_at = Instant::now();
+            circuit.trials_in_flight = 0;
+        }
+        CircuitEvent {
+            host: host.to_string(),
+            from,
+            to,
+        }
+    }
+
+    fn emit(&self, event: Option<CircuitEvent>) {
+        if let (Some(event), Some(callback)) = (event, &self.on_state_change) {
+            callback(&event);
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, HostCircuit>> {
+        self.hosts.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for CircuitBreaker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CircuitBreaker")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+/// Returns the key used to identify a host, including a non-default port.
+///
+/// # Example
+///
+/// ```
+/// use booru_rs::circuit::host_key;
+/// use reqwest::Url;
+///
+/// let url = Url::parse("https://gelbooru.com/index.php?page=dapi").unwrap();
+/// assert_eq!(host_key(&url), "gelbooru.com");
+///
+/// let url = Url::parse("http://127.0.0.1:8080/posts.json").unwrap();
+/// assert_eq!(host_key(&url), "127.0.0.1:8080");
+/// ```
+#[must_use]
+pub fn host_key(url: &Url) -> String {
+    let host = url.host_str().unwrap_or_default();
+    match url.port() {
+        Some(port) => format!("{host}:{port}"),
+        None => host.to_string(),
+    }
+}
+
+/// Returns `true` if a response status indicates the host is unhealthy.
+fn is_failure_status(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker(cool_down: Duration) -> CircuitBreaker {
+        CircuitBreaker::with_config(CircuitBreakerConfig {
+            failure_rate_threshold: 0.5,
+            minimum_requests: 4,
+            window_size: 4,
+            cool_down,
+            half_open_max_requests: 1,
+        })
+    }
+
+    #[test]
+    fn test_opens_on_failure_rate() {
+        let breaker = breaker(Duration::from_secs(60));
+
+        breaker.record_success("a");
+        breaker.record_failure("a");
+        breaker.record_success("a");
+        assert_eq!(breaker.state("a"), CircuitState::Closed);
+
+        breaker.record_failure("a");
+        assert_eq!(breaker.state("a"), CircuitState::Open);
+
+        let err = breaker.check("a").unwrap_err();
+        assert!(matches!(err, BooruError::CircuitOpen { ref host, .. } if host == "a"));
+
+        // Other hosts are unaffected
+        assert!(breaker.check("b").is_ok());
+    }
+
+    #[test]
+    fn test_minimum_requests() {
+        let breaker = breaker(Duration::from_secs(60));
+
+        breaker.record_failure("a");
+        breaker.record_failure("a");
+        breaker.record_failure("a");
+        assert_eq!(breaker.state("a"), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_half_open_recovery() {
+        let breaker = breaker(Duration::ZERO);
+
+        for _ in 0..4 {
+            breaker.record_failure("a");
+        }
+        assert_eq!(breaker.state("a"), CircuitState::HalfOpen);
+
+        // One trial request is allowed, the next is rejected
+        assert!(breaker.check("a").is_ok());
+        assert!(breaker.check("a").is_err());
+
+        breaker.record_success("a");
+        assert_eq!(breaker.state("a"), CircuitState::Closed);
+        assert!(breaker.check("a").is_ok());
+    }
+
+    #[test]
+    fn test_half_open_failure_reopens() {
+        let breaker = breaker(Duration::from_millis(20));
+
+        for _ in 0..4 {
+            breaker.record_failure("a");
+        }
+        std::thread::sleep(Duration::from_millis(30));
+
+        assert!(breaker.check("a").is_ok());
+        breaker.record_failure("a");
+        assert_eq!(breaker.state("a"), CircuitState::Open);
+    }
+
+    #[test]
+    fn test_state_change_events() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let breaker = breaker(Duration::ZERO).on_state_change({
+            let events = events.clone();
+            move |event| events.lock().unwrap().push((event.from, event.to))
+        });
+
+        for _ in 0..4 {
+            breaker.record_failure("a");
+        }
+        breaker.check("a").unwrap();
+        breaker.record_success("a");
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                (CircuitState::Closed, CircuitState::Open),
+                (CircuitState::Open, CircuitState::HalfOpen),
+                (CircuitState::HalfOpen, CircuitState::Closed),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let breaker = breaker(Duration::from_secs(60));
+
+        for _ in 0..4 {
+            breaker.record_failure("a");
+        }
+        breaker.reset("a");
+        assert_eq!(breaker.state("a"), CircuitState::Closed);
+    }
+}
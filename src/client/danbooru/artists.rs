@@ -0,0 +1,190 @@
+//! Danbooru artist records (`/artists.json`).
+
+use super::{DanbooruClient, get_json};
+use crate::client::ClientBuilder;
+use crate::error::Result;
+use crate::model::danbooru::DanbooruArtist;
+use crate::stream::paginated_query;
+
+/// Fields requested for artists, so that their URLs are included.
+const ARTIST_FIELDS: &str =
+    "id,name,group_name,other_names,is_banned,is_deleted,created_at,updated_at,urls";
+
+impl DanbooruClient {
+    /// Retrieves a single artist by its ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::ApiError`] with status 404 if no artist exists
+    /// with the given ID (see [`BooruError::is_not_found`]).
+    /// Returns other errors if the request fails or the response cannot be parsed.
+    ///
+    /// [`BooruError::ApiError`]: crate::error::BooruError::ApiError
+    /// [`BooruError::is_not_found`]: crate::error::BooruError::is_not_found
+    pub async fn artist(&self, id: u32) -> Result<DanbooruArtist> {
+        get_json(
+            &self.0,
+            &format!("artists/{id}.json"),
+            &[("only", ARTIST_FIELDS.to_string())],
+        )
+        .await
+    }
+
+    /// Starts a search of Danbooru's artist records.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::danbooru::DanbooruClient;
+    /// use booru_rs::client::Client;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// // Which artist posts on this account?
+    /// let artists = DanbooruClient::builder()
+    ///     .build()
+    ///     .artists()
+    ///     .url_matches("https://www.pixiv.net/users/123456")
+    ///     .get()
+    ///     .await?;
+    ///
+    /// for artist in artists {
+    ///     println!("{} (aka {})", artist.name, artist.other_names.join(", "));
+    ///     for url in artist.active_urls() {
+    ///         println!("  {url}");
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn artists(&self) -> DanbooruArtistQuery {
+        DanbooruArtistQuery::new(self.0.clone())
+    }
+}
+
+/// A search of Danbooru's artist records.
+///
+/// Created by [`DanbooruClient::artists`].
+#[derive(Debug, Clone)]
+pub struct DanbooruArtistQuery {
+    builder: ClientBuilder<DanbooruClient>,
+    name: Option<String>,
+    url_matches: Option<String>,
+    other_name: Option<String>,
+    any_name_matches: Option<String>,
+    is_banned: Option<bool>,
+    is_deleted: Option<bool>,
+    limit: u32,
+    page: u32,
+}
+
+impl DanbooruArtistQuery {
+    fn new(builder: ClientBuilder<DanbooruClient>) -> Self {
+        Self {
+            builder,
+            name: None,
+            url_matches: None,
+            other_name: None,
+            any_name_matches: None,
+            is_banned: None,
+            is_deleted: None,
+            limit: 100,
+            page: 1,
+        }
+    }
+
+    /// Only returns the artist whose tag has exactly this name.
+    #[must_use]
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Only returns artists with a website matching a URL.
+    ///
+    /// Pass a full profile or post URL (e.g. a Pixiv or Twitter link) to find
+    /// the artist behind it. `*` matches any sequence of characters.
+    #[must_use]
+    pub fn url_matches(mut self, url: impl Into<String>) -> Self {
+        self.url_matches = Some(url.into());
+        self
+    }
+
+    /// Only returns artists with a matching other name.
+    ///
+    /// `*` matches any sequence of characters.
+    #[must_use]
+    pub fn other_name(mut self, name: impl Into<String>) -> Self {
+        self.other_name = Some(name.into());
+        self
+    }
+
+    /// Only returns artists whose name, group name or other names match.
+    ///
+    /// `*` matches any sequence of characters.
+    #[must_use]
+    pub fn any_name_matches(mut self, pattern: impl Into<String>) -> Self {
+        self.any_name_matches = Some(pattern.into());
+        self
+    }
+
+    /// Only returns banned (`true`) or non-banned (`false`) artists.
+    #[must_use]
+    pub fn banned(mut self, banned: bool) -> Self {
+        self.is_banned = Some(banned);
+        self
+    }
+
+    /// Only returns deleted (`true`) or non-deleted (`false`) artists.
+    #[must_use]
+    pub fn deleted(mut self, deleted: bool) -> Self {
+        self.is_deleted = Some(deleted);
+        self
+    }
+
+    /// Fetches the configured page of artists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or if the response cannot be parsed.
+    pub async fn get(&self) -> Result<Vec<DanbooruArtist>> {
+        get_json(&self.builder, "artists.json", &self.params()).await
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![
+            ("only", ARTIST_FIELDS.to_string()),
+            ("limit", self.limit.to_string()),
+            ("page", self.page.to_string()),
+        ];
+
+        if let Some(name) = &self.name {
+            params.push(("search[name]", name.clone()));
+        }
+        if let Some(url) = &self.url_matches {
+            params.push(("search[url_matches]", url.clone()));
+        }
+        if let Some(name) = &self.other_name {
+            params.push(("search[any_other_name_like]", name.clone()));
+        }
+        if let Some(pattern) = &self.any_name_matches {
+            params.push(("search[any_name_matches]", pattern.clone()));
+        }
+        if let Some(banned) = self.is_banned {
+            params.push(("search[is_banned]", banned.to_string()));
+        }
+        if let Some(deleted) = self.is_deleted {
+            params.push(("search[is_deleted]", deleted.to_string()));
+        }
+
+        params
+    }
+}
+
+paginated_query! {
+    DanbooruArtistQuery => DanbooruArtist,
+    noun: "artists",
+    stream: into_artist_stream,
+    limit: "Default is 100.",
+    page: "Danbooru page numbering starts at 1, which is the default.",
+}
@@ -0,0 +1,138 @@
+//! Danbooru comments (`/comments.json`).
+
+use super::{DanbooruClient, get_json};
+use crate::client::ClientBuilder;
+use crate::error::Result;
+use crate::model::danbooru::DanbooruComment;
+use crate::stream::paginated_query;
+
+impl DanbooruClient {
+    /// Starts a search of Danbooru's comments.
+    ///
+    /// Comments are returned newest first.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::danbooru::DanbooruClient;
+    /// use booru_rs::client::Client;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// let comments = DanbooruClient::builder()
+    ///     .build()
+    ///     .comments()
+    ///     .post_id(12345)
+    ///     .deleted(false)
+    ///     .get()
+    ///     .await?;
+    ///
+    /// for comment in comments {
+    ///     println!("[{}] {}", comment.score, comment.body);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn comments(&self) -> DanbooruCommentQuery {
+        DanbooruCommentQuery::new(self.0.clone())
+    }
+}
+
+/// A search of Danbooru's comments.
+///
+/// Created by [`DanbooruClient::comments`].
+#[derive(Debug, Clone)]
+pub struct DanbooruCommentQuery {
+    builder: ClientBuilder<DanbooruClient>,
+    post_id: Option<u32>,
+    creator_name: Option<String>,
+    body_matches: Option<String>,
+    is_deleted: Option<bool>,
+    limit: u32,
+    page: u32,
+}
+
+impl DanbooruCommentQuery {
+    fn new(builder: ClientBuilder<DanbooruClient>) -> Self {
+        Self {
+            builder,
+            post_id: None,
+            creator_name: None,
+            body_matches: None,
+            is_deleted: None,
+            limit: 100,
+            page: 1,
+        }
+    }
+
+    /// Only returns comments on the given post.
+    #[must_use]
+    pub fn post_id(mut self, post_id: u32) -> Self {
+        self.post_id = Some(post_id);
+        self
+    }
+
+    /// Only returns comments written by the given user.
+    #[must_use]
+    pub fn creator_name(mut self, name: impl Into<String>) -> Self {
+        self.creator_name = Some(name.into());
+        self
+    }
+
+    /// Only returns comments whose text matches a pattern.
+    ///
+    /// `*` matches any sequence of characters.
+    #[must_use]
+    pub fn body_matches(mut self, pattern: impl Into<String>) -> Self {
+        self.body_matches = Some(pattern.into());
+        self
+    }
+
+    /// Only returns deleted (`true`) or non-deleted (`false`) comments.
+    #[must_use]
+    pub fn deleted(mut self, deleted: bool) -> Self {
+        self.is_deleted = Some(deleted);
+        self
+    }
+
+    /// Fetches the configured page of comments.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or if the response cannot be parsed.
+    pub async fn get(&self) -> Result<Vec<DanbooruComment>> {
+        get_json(&self.builder, "comments.json", &self.params()).await
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        // Without `group_by=comment` Danbooru may group the results by post.
+        let mut params = vec![
+            ("group_by", "comment".to_string()),
+            ("limit", self.limit.to_string()),
+            ("page", self.page.to_string()),
+        ];
+
+        if let Some(post_id) = self.post_id {
+            params.push(("search[post_id]", post_id.to_string()));
+        }
+        if let Some(name) = &self.creator_name {
+            params.push(("search[creator_name]", name.clone()));
+        }
+        if let Some(pattern) = &self.body_matches {
+            params.push(("search[body_matches]", pattern.clone()));
+        }
+        if let Some(deleted) = self.is_deleted {
+            params.push(("search[is_deleted]", deleted.to_string()));
+        }
+
+        params
+    }
+}
+
+paginated_query! {
+    DanbooruCommentQuery => DanbooruComment,
+    noun: "comments",
+    stream: into_comment_stream,
+    limit: "Default is 100.",
+    page: "Danbooru page numbering starts at 1, which is the default.",
+}
@@ -0,0 +1,65 @@
+//! Danbooru post counts (`/counts/posts.json`).
+
+use serde::Deserialize;
+
+use super::{DanbooruClient, get_json};
+use crate::client::ClientBuilder;
+use crate::error::{BooruError, Result};
+
+#[derive(Debug, Deserialize)]
+struct CountsResponse {
+    counts: Counts,
+}
+
+#[derive(Debug, Deserialize)]
+struct Counts {
+    posts: Option<u64>,
+}
+
+impl ClientBuilder<DanbooruClient> {
+    /// Returns the total number of posts matching the configured tags.
+    ///
+    /// The limit and page settings are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::EmptyResponse`] if Danbooru could not count the
+    /// posts (e.g. the search timed out). Returns other errors if the request
+    /// fails or the response cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::danbooru::DanbooruClient;
+    /// use booru_rs::client::Client;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// let builder = DanbooruClient::builder().tag("cat_ears")?.limit(100);
+    /// let total = builder.count().await?;
+    /// let posts = builder.build().get().await?;
+    ///
+    /// println!("1-{} of {}", posts.len(), total);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn count(&self) -> Result<u64> {
+        let response: CountsResponse =
+            get_json(self, "counts/posts.json", &[("tags", self.tags.join(" "))]).await?;
+
+        response.counts.posts.ok_or(BooruError::EmptyResponse)
+    }
+}
+
+impl DanbooruClient {
+    /// Returns the total number of posts matching the configured tags.
+    ///
+    /// See [`ClientBuilder::count`](ClientBuilder#method.count).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if Danbooru could not count the posts, the request
+    /// fails, or the response cannot be parsed.
+    pub async fn count(&self) -> Result<u64> {
+        self.0.count().await
+    }
+}
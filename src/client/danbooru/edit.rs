@@ -0,0 +1,255 @@
+//! Danbooru post editing (`PUT /posts/{id}.json`).
+
+use reqwest::Method;
+
+use super::{DanbooruClient, rating_code, request, require_credentials};
+use crate::client::ClientBuilder;
+use crate::error::Result;
+use crate::model::danbooru::{DanbooruPost, DanbooruRating};
+
+impl DanbooruClient {
+    /// Starts an edit of a post.
+    ///
+    /// The edit is computed against the post as given, and Danbooru is told
+    /// which tags the post had at that point (`old_tag_string`). If someone
+    /// else changed the tags in the meantime, Danbooru merges both edits
+    /// instead of overwriting theirs.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::danbooru::{DanbooruClient, DanbooruRating};
+    /// use booru_rs::client::Client;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// let client = DanbooruClient::builder()
+    ///     .set_credentials("your_api_key", "your_login")
+    ///     .build();
+    /// let post = client.get_by_id(12345).await?;
+    ///
+    /// let edit = client
+    ///     .edit_post(&post)
+    ///     .add_tags(["cat_ears"])
+    ///     .remove_tags(["animal_ears"])
+    ///     .rating(DanbooruRating::General);
+    ///
+    /// // Preview the change without sending anything.
+    /// let diff = edit.dry_run();
+    /// println!("+{:?} -{:?}", diff.added_tags, diff.removed_tags);
+    ///
+    /// if !diff.is_empty() {
+    ///     edit.send().await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn edit_post(&self, post: &DanbooruPost) -> DanbooruPostEdit {
+        DanbooruPostEdit {
+            builder: self.0.clone(),
+            post_id: post.id,
+            old_tags: post
+                .tag_string
+                .split_whitespace()
+                .map(String::from)
+                .collect(),
+            old_rating: post.rating,
+            old_source: post.source.clone(),
+            old_parent_id: post.parent_id,
+            add: Vec::new(),
+            remove: Vec::new(),
+            rating: None,
+            source: None,
+            parent_id: None,
+        }
+    }
+}
+
+/// An edit of a Danbooru post.
+///
+/// Created by [`DanbooruClient::edit_post`]. Use [`dry_run`](Self::dry_run)
+/// to see what would change and [`send`](Self::send) to apply the edit.
+#[derive(Debug, Clone)]
+pub struct DanbooruPostEdit {
+    builder: ClientBuilder<DanbooruClient>,
+    post_id: u32,
+    old_tags: Vec<String>,
+    old_rating: Option<DanbooruRating>,
+    old_source: String,
+    old_parent_id: Option<u32>,
+    add: Vec<String>,
+    remove: Vec<String>,
+    rating: Option<DanbooruRating>,
+    source: Option<String>,
+    parent_id: Option<Option<u32>>,
+}
+
+impl DanbooruPostEdit {
+    /// Adds tags to the post.
+    #[must_use]
+    pub fn add_tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.add.extend(tags.into_iter().map(Into::into));
+        self
+    }
+
+    /// Removes tags from the post.
+    ///
+    /// Removing a tag takes precedence over adding it.
+    #[must_use]
+    pub fn remove_tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.remove.extend(tags.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets the rating of the post.
+    #[must_use]
+    pub fn rating(mut self, rating: DanbooruRating) -> Self {
+        self.rating = Some(rating);
+        self
+    }
+
+    /// Sets the source of the post.
+    #[must_use]
+    pub fn source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Sets the parent of the post, or removes it with `None`.
+    #[must_use]
+    pub fn parent_id(mut self, parent_id: Option<u32>) -> Self {
+        self.parent_id = Some(parent_id);
+        self
+    }
+
+    /// Returns the changes this edit would make, without sending anything.
+    #[must_use]
+    pub fn dry_run(&self) -> DanbooruPostDiff {
+        let mut tags = self.old_tags.clone();
+        for tag in &self.add {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+        tags.retain(|tag| !self.remove.contains(tag));
+
+        let added_tags = tags
+            .iter()
+            .filter(|tag| !self.old_tags.contains(tag))
+            .cloned()
+            .collect();
+        let removed_tags = self
+            .old_tags
+            .iter()
+            .filter(|tag| !tags.contains(tag))
+            .cloned()
+            .collect();
+
+        DanbooruPostDiff {
+            post_id: self.post_id,
+            tag_string: tags.join(" "),
+            added_tags,
+            removed_tags,
+            rating: self
+                .rating
+                .filter(|rating| Some(*rating) != self.old_rating)
+                .map(|rating| (self.old_rating, rating)),
+            source: self
+                .source
+                .clone()
+                .filter(|source| *source != self.old_source)
+                .map(|source| (self.old_source.clone(), source)),
+            parent_id: self
+                .parent_id
+                .filter(|parent_id| *parent_id != self.old_parent_id)
+                .map(|parent_id| (self.old_parent_id, parent_id)),
+        }
+    }
+
+    /// Applies the edit and returns the updated post.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::Unauthorized`] if no credentials are set or
+    /// they are invalid, [`BooruError::Forbidden`] if the account may not
+    /// edit the post, and other errors if the request fails or the
+    /// response cannot be parsed.
+    ///
+    /// [`BooruError::Unauthorized`]: crate::error::BooruError::Unauthorized
+    /// [`BooruError::Forbidden`]: crate::error::BooruError::Forbidden
+    pub async fn send(&self) -> Result<DanbooruPost> {
+        require_credentials(&self.builder)?;
+
+        let diff = self.dry_run();
+        let mut params = vec![
+            ("post[tag_string]", diff.tag_string),
+            ("post[old_tag_string]", self.old_tags.join(" ")),
+        ];
+
+        if let Some((old, new)) = diff.rating {
+            params.push(("post[rating]", rating_code(new).to_string()));
+            if let Some(old) = old {
+                params.push(("post[old_rating]", rating_code(old).to_string()));
+            }
+        }
+        if let Some((old, new)) = diff.source {
+            params.push(("post[source]", new));
+            params.push(("post[old_source]", old));
+        }
+        if let Some((old, new)) = diff.parent_id {
+            let id =
+                |parent_id: Option<u32>| parent_id.map(|id| id.to_string()).unwrap_or_default();
+            params.push(("post[parent_id]", id(new)));
+            params.push(("post[old_parent_id]", id(old)));
+        }
+
+        let path = format!("posts/{}.json", self.post_id);
+        let response = self
+            .builder
+            .send(request(&self.builder, Method::PUT, &path).form(&params))
+            .await?;
+
+        Ok(response.json().await?)
+    }
+}
+
+/// The changes made by a [`DanbooruPostEdit`].
+///
+/// Changed fields are given as `(old, new)` pairs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DanbooruPostDiff {
+    /// The ID of the edited post
+    pub post_id: u32,
+    /// Full tag string after the edit
+    pub tag_string: String,
+    /// Tags the edit adds
+    pub added_tags: Vec<String>,
+    /// Tags the edit removes
+    pub removed_tags: Vec<String>,
+    /// Rating change, if any
+    pub rating: Option<(Option<DanbooruRating>, DanbooruRating)>,
+    /// Source change, if any
+    pub source: Option<(String, String)>,
+    /// Parent change, if any
+    pub parent_id: Option<(Option<u32>, Option<u32>)>,
+}
+
+impl DanbooruPostDiff {
+    /// Returns `true` if the edit changes nothing.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added_tags.is_empty()
+            && self.removed_tags.is_empty()
+            && self.rating.is_none()
+            && self.source.is_none()
+            && self.parent_id.is_none()
+    }
+}
@@ -0,0 +1,186 @@
+//! Danbooru explore feeds (`/explore/posts/*.json`).
+
+use serde_json::Value;
+
+use super::{DanbooruClient, get_json};
+use crate::client::ClientBuilder;
+use crate::error::{BooruError, Result};
+use crate::model::danbooru::{DanbooruExploreScale, DanbooruPost};
+use crate::model::lenient::decode_items;
+use crate::stream::paginated_query;
+
+impl DanbooruClient {
+    /// Starts a query for the most popular posts, ranked by score.
+    ///
+    /// Defaults to today's posts.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::danbooru::{DanbooruClient, DanbooruExploreScale};
+    /// use booru_rs::client::Client;
+    /// use booru_rs::download::Downloader;
+    /// use std::path::Path;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// let posts = DanbooruClient::builder()
+    ///     .build()
+    ///     .popular()
+    ///     .date("2024-06-01")?
+    ///     .scale(DanbooruExploreScale::Week)
+    ///     .get()
+    ///     .await?;
+    ///
+    /// Downloader::new()
+    ///     .download_posts(&posts, Path::new("./popular"), 4)
+    ///     .await;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn popular(&self) -> DanbooruExploreQuery {
+        DanbooruExploreQuery::new(self.0.clone(), "explore/posts/popular.json")
+    }
+
+    /// Starts a query for curated posts, ranked by how many moderators
+    /// favorited them.
+    ///
+    /// Defaults to today's posts.
+    #[must_use]
+    pub fn curated(&self) -> DanbooruExploreQuery {
+        DanbooruExploreQuery::new(self.0.clone(), "explore/posts/curated.json")
+    }
+
+    /// Starts a query for the most viewed posts of a day.
+    ///
+    /// Defaults to today's posts. Danbooru only ranks views per day, so
+    /// [`scale`](DanbooruExploreQuery::scale) has no effect on this feed.
+    #[must_use]
+    pub fn most_viewed(&self) -> DanbooruExploreQuery {
+        DanbooruExploreQuery::new(self.0.clone(), "explore/posts/viewed.json")
+    }
+}
+
+/// A query for one of Danbooru's explore feeds.
+///
+/// Created by [`DanbooruClient::popular`], [`DanbooruClient::curated`] or
+/// [`DanbooruClient::most_viewed`].
+#[derive(Debug, Clone)]
+pub struct DanbooruExploreQuery {
+    builder: ClientBuilder<DanbooruClient>,
+    endpoint: &'static str,
+    date: Option<String>,
+    scale: Option<DanbooruExploreScale>,
+    limit: u32,
+    page: u32,
+}
+
+impl DanbooruExploreQuery {
+    fn new(builder: ClientBuilder<DanbooruClient>, endpoint: &'static str) -> Self {
+        Self {
+            builder,
+            endpoint,
+            date: None,
+            scale: None,
+            limit: 20,
+            page: 1,
+        }
+    }
+
+    /// Sets the date of the feed, as `YYYY-MM-DD`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::InvalidDate`] if `date` is not a `YYYY-MM-DD`
+    /// date.
+    pub fn date(mut self, date: impl Into<String>) -> Result<Self> {
+        let date = date.into();
+        if !is_valid_date(&date) {
+            return Err(BooruError::InvalidDate(date));
+        }
+
+        self.date = Some(date);
+        Ok(self)
+    }
+
+    /// Sets the time span of the feed.
+    ///
+    /// Default is [`DanbooruExploreScale::Day`].
+    #[must_use]
+    pub fn scale(mut self, scale: DanbooruExploreScale) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+
+    /// Fetches the posts of the feed, highest ranked first.
+    ///
+    /// Respects [`ClientBuilder::lenient`], skipping posts that fail to
+    /// decode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or if the response cannot be parsed.
+    pub async fn get(&self) -> Result<Vec<DanbooruPost>> {
+        if self.builder.lenient {
+            let items: Vec<Value> = get_json(&self.builder, self.endpoint, &self.params()).await?;
+            return Ok(decode_items(items).posts);
+        }
+
+        get_json(&self.builder, self.endpoint, &self.params()).await
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![
+            ("limit", self.limit.to_string()),
+            ("page", self.page.to_string()),
+        ];
+
+        if let Some(date) = &self.date {
+            params.push(("date", date.clone()));
+        }
+        if let Some(scale) = self.scale {
+            params.push(("scale", scale.to_string()));
+        }
+
+        params
+    }
+}
+
+paginated_query! {
+    DanbooruExploreQuery => DanbooruPost,
+    noun: "posts",
+    stream: into_post_stream,
+    limit: "Default is 20.",
+    page: "Danbooru page numbering starts at 1, which is the default.",
+}
+
+/// Returns whether `date` is a calendar date written as `YYYY-MM-DD`.
+fn is_valid_date(date: &str) -> bool {
+    let bytes = date.as_bytes();
+    if bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return false;
+    }
+
+    let number = |range: std::ops::Range<usize>| -> Option<u32> {
+        let digits = &date[range];
+        digits
+            .bytes()
+            .all(|b| b.is_ascii_digit())
+            .then(|| digits.parse().ok())
+            .flatten()
+    };
+    let (Some(year), Some(month), Some(day)) = (number(0..4), number(5..7), number(8..10)) else {
+        return false;
+    };
+
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return false,
+    };
+
+    (1..=days_in_month).contains(&day)
+}
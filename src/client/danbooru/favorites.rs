@@ -0,0 +1,100 @@
+//! Danbooru favorites (`/favorites.json`).
+
+use reqwest::Method;
+
+use super::{DanbooruClient, get_json, request, require_credentials};
+use crate::error::Result;
+use crate::model::danbooru::{DanbooruFavorite, DanbooruPost};
+
+impl DanbooruClient {
+    /// Adds a post to the authenticated user's favorites.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::Unauthorized`] if no credentials are set or
+    /// they are invalid, and [`BooruError::Forbidden`] if the account may
+    /// not favorite posts. Returns other errors if the request fails or the
+    /// response cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::danbooru::DanbooruClient;
+    /// use booru_rs::client::Client;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// let client = DanbooruClient::builder()
+    ///     .set_credentials("your_api_key", "your_login")
+    ///     .build();
+    ///
+    /// client.add_favorite(12345).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`BooruError::Unauthorized`]: crate::error::BooruError::Unauthorized
+    /// [`BooruError::Forbidden`]: crate::error::BooruError::Forbidden
+    pub async fn add_favorite(&self, post_id: u32) -> Result<DanbooruFavorite> {
+        let builder = &self.0;
+        require_credentials(builder)?;
+
+        let favorite = builder
+            .send(
+                request(builder, Method::POST, "favorites.json")
+                    .query(&[("post_id", post_id.to_string())]),
+            )
+            .await?
+            .json::<DanbooruFavorite>()
+            .await?;
+
+        Ok(favorite)
+    }
+
+    /// Removes a post from the authenticated user's favorites.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::Unauthorized`] if no credentials are set or
+    /// they are invalid, and [`BooruError::Forbidden`] if the account lacks
+    /// permission. Returns other errors if the request fails.
+    ///
+    /// [`BooruError::Unauthorized`]: crate::error::BooruError::Unauthorized
+    /// [`BooruError::Forbidden`]: crate::error::BooruError::Forbidden
+    pub async fn remove_favorite(&self, post_id: u32) -> Result<()> {
+        let builder = &self.0;
+        require_credentials(builder)?;
+
+        builder
+            .send(request(
+                builder,
+                Method::DELETE,
+                &format!("favorites/{post_id}.json"),
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Retrieves a user's favorite posts, most recently favorited first.
+    ///
+    /// Uses the client's configured limit and page. Only public favorites
+    /// are visible, unless the client is authenticated as that user.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or if the response cannot be parsed.
+    pub async fn favorites(&self, username: &str) -> Result<Vec<DanbooruPost>> {
+        let builder = &self.0;
+
+        get_json(
+            builder,
+            "posts.json",
+            &[
+                ("tags", format!("ordfav:{username}")),
+                ("limit", builder.limit.to_string()),
+                ("page", builder.page.to_string()),
+            ],
+        )
+        .await
+    }
+}
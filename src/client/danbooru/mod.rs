@@ -0,0 +1,356 @@
+//! Danbooru API client implementation.
+
+use super::{
+    Client, ClientBuilder, PostsById, RANDOM_ATTEMPTS_PER_POST, error_for_status,
+    get_by_ids_chunked, get_by_md5_search, post_not_found, shared_client,
+};
+use crate::autocomplete::{Autocomplete, TagSuggestion};
+use crate::error::{BooruError, Result};
+use crate::model::danbooru::*;
+use crate::model::lenient::{LenientPosts, decode_items};
+
+use reqwest::Method;
+use reqwest::header::{self, HeaderMap, HeaderValue};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+mod artists;
+mod comments;
+mod counts;
+mod edit;
+mod explore;
+mod favorites;
+mod notes;
+mod pools;
+mod post_versions;
+mod related;
+mod resolver;
+mod saved_searches;
+mod tags;
+mod uploads;
+mod votes;
+mod wiki;
+
+pub use artists::*;
+pub use comments::*;
+pub use edit::*;
+pub use explore::*;
+pub use notes::*;
+pub use pools::*;
+pub use post_versions::*;
+pub use related::*;
+pub use resolver::*;
+pub use saved_searches::*;
+pub use tags::*;
+pub use uploads::*;
+
+/// Returns headers required for Danbooru API requests.
+///
+/// Danbooru requires a User-Agent header for requests.
+fn get_headers() -> HeaderMap {
+    let mut headers = HeaderMap::with_capacity(1);
+    headers.insert(
+        header::USER_AGENT,
+        HeaderValue::from_static("booru-rs/0.3.0"),
+    );
+    headers
+}
+
+/// Starts a request to a Danbooru endpoint.
+///
+/// If credentials are set, they are sent with HTTP basic auth (login as
+/// the username, API key as the password).
+fn request(
+    builder: &ClientBuilder<DanbooruClient>,
+    method: Method,
+    path: &str,
+) -> reqwest::RequestBuilder {
+    let url = &builder.url;
+    let request = builder
+        .client
+        .request(method, format!("{url}/{path}"))
+        .headers(get_headers());
+
+    match (&builder.user, &builder.key) {
+        (Some(login), Some(key)) => request.basic_auth(login, Some(key)),
+        _ => request,
+    }
+}
+
+/// Sends a GET request to a Danbooru JSON endpoint and decodes the response.
+async fn get_json<R: DeserializeOwned>(
+    builder: &ClientBuilder<DanbooruClient>,
+    path: &str,
+    query: &[(&str, String)],
+) -> Result<R> {
+    let response = builder
+        .send(request(builder, Method::GET, path).query(query))
+        .await?
+        .json::<R>()
+        .await?;
+
+    Ok(response)
+}
+
+/// Percent-encodes a value for use as a single URL path segment.
+///
+/// Dots are encoded as well, since Danbooru would otherwise read the part
+/// after a dot as the response format.
+fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'~' => {
+                encoded.push(byte as char);
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// Returns the single-letter code Danbooru uses for a rating.
+fn rating_code(rating: DanbooruRating) -> &'static str {
+    match rating {
+        DanbooruRating::Explicit => "e",
+        DanbooruRating::Questionable => "q",
+        DanbooruRating::Sensitive => "s",
+        DanbooruRating::General => "g",
+    }
+}
+
+/// Fails early with a helpful message if no credentials are set.
+fn require_credentials(builder: &ClientBuilder<DanbooruClient>) -> Result<()> {
+    if builder.user.is_some() && builder.key.is_some() {
+        Ok(())
+    } else {
+        Err(BooruError::Unauthorized(
+            "This Danbooru action requires an account. Use set_credentials(api_key, login)".into(),
+        ))
+    }
+}
+
+/// Client for interacting with the Danbooru API.
+///
+/// Danbooru has a limit of 2 tags per query for non-authenticated users.
+///
+/// # Authentication
+///
+/// Pass your API key and login to [`ClientBuilder::set_credentials`] to
+/// authenticate requests with HTTP basic auth. Authentication is required
+/// for write operations such as [`add_favorite`](Self::add_favorite) and
+/// [`upvote`](Self::upvote). API keys can be created from your
+/// [Danbooru profile](https://danbooru.donmai.us/profile).
+///
+/// # Example
+///
+/// ```no_run
+/// use booru_rs::danbooru::{DanbooruClient, DanbooruRating};
+/// use booru_rs::client::Client;
+///
+/// # async fn example() -> booru_rs::error::Result<()> {
+/// let posts = DanbooruClient::builder()
+///     .tag("cat_ears")?
+///     .rating(DanbooruRating::General)
+///     .limit(10)
+///     .build()
+///     .get()
+///     .await?;
+///
+/// println!("Found {} posts", posts.len());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DanbooruClient(ClientBuilder<Self>);
+
+impl From<ClientBuilder<Self>> for DanbooruClient {
+    fn from(value: ClientBuilder<Self>) -> Self {
+        Self(value)
+    }
+}
+
+impl Client for DanbooruClient {
+    type Post = DanbooruPost;
+    type Rating = DanbooruRating;
+
+    const URL: &'static str = "https://danbooru.donmai.us";
+    const SORT: &'static str = "order:";
+    const MAX_TAGS: Option<usize> = Some(2);
+
+    /// Retrieves a single post by its unique ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::PostNotFound`] if no post exists with the given ID.
+    /// Returns other errors if the request fails or if the response cannot be parsed.
+    ///
+    /// [`BooruError::PostNotFound`]: crate::error::BooruError::PostNotFound
+    async fn get_by_id(&self, id: u32) -> Result<Self::Post> {
+        let builder = &self.0;
+
+        let response = builder
+            .send(request(builder, Method::GET, &format!("posts/{id}.json")))
+            .await
+            .map_err(post_not_found(id))?
+            .json::<DanbooruPost>()
+            .await?;
+
+        Ok(response)
+    }
+
+    /// Retrieves many posts by ID with `id:1,2,3` searches of up to 200 IDs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails or if a response cannot be parsed.
+    async fn get_by_ids(&self, ids: &[u32]) -> Result<PostsById<Self::Post>> {
+        get_by_ids_chunked(&self.0, ids, 200, |ids| {
+            let ids: Vec<String> = ids.iter().map(u32::to_string).collect();
+            format!("id:{}", ids.join(","))
+        })
+        .await
+    }
+
+    /// Retrieves the post whose file has the given MD5 hash.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::InvalidTag`] if `md5` is not a valid MD5 hash,
+    /// and other errors if the request fails or the response cannot be parsed.
+    async fn get_by_md5(&self, md5: &str) -> Result<Option<Self::Post>> {
+        get_by_md5_search(&self.0, md5).await
+    }
+
+    /// Retrieves random posts from `/posts/random.json`, one request per post.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails or if a response cannot be parsed.
+    async fn random(&self, count: u32) -> Result<Vec<Self::Post>> {
+        let builder = &self.0;
+        let tag_string = builder.tags_without_sort().join(" ");
+
+        let mut posts = Vec::new();
+        for _ in 0..count.saturating_mul(RANDOM_ATTEMPTS_PER_POST) {
+            if posts.len() >= count as usize {
+                break;
+            }
+
+            let response = builder
+                .send(
+                    request(builder, Method::GET, "posts/random.json")
+                        .query(&[("tags", &tag_string)]),
+                )
+                .await;
+            let post = match response {
+                Ok(response) => response.json::<DanbooruPost>().await?,
+                // No post matches the tags
+                Err(error) if error.is_not_found() => break,
+                Err(error) => return Err(error),
+            };
+
+            if builder.remember_random(post.id) {
+                posts.push(post);
+            }
+        }
+
+        Ok(posts)
+    }
+
+    /// Retrieves posts matching the configured query.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or if the response cannot be parsed.
+    async fn get(&self) -> Result<Vec<Self::Post>> {
+        if self.0.lenient {
+            return Ok(self.get_lenient().await?.posts);
+        }
+
+        Ok(self.fetch_page().await?.json::<Vec<DanbooruPost>>().await?)
+    }
+
+    /// Retrieves posts matching the configured query, decoding each post
+    /// separately.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or if the response is not a JSON array.
+    async fn get_lenient(&self) -> Result<LenientPosts<Self::Post>> {
+        let items = self.fetch_page().await?.json::<Vec<Value>>().await?;
+
+        Ok(decode_items(items))
+    }
+}
+
+impl DanbooruClient {
+    /// Sends the configured post search.
+    async fn fetch_page(&self) -> Result<reqwest::Response> {
+        let builder = &self.0;
+        let tag_string = builder.tags.join(" ");
+
+        builder
+            .send(request(builder, Method::GET, "posts.json").query(&[
+                ("limit", builder.limit.to_string()),
+                ("page", builder.page.to_string()),
+                ("tags", tag_string),
+            ]))
+            .await
+    }
+}
+
+/// Danbooru autocomplete API response item.
+#[derive(Debug, Deserialize)]
+struct DanbooruAutocompleteItem {
+    value: String,
+    label: String,
+    category: Option<u8>,
+    post_count: Option<u32>,
+}
+
+impl Autocomplete for DanbooruClient {
+    /// Returns tag suggestions from Danbooru's autocomplete API.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::danbooru::DanbooruClient;
+    /// use booru_rs::autocomplete::Autocomplete;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// let suggestions = DanbooruClient::autocomplete("cat_", 10).await?;
+    /// for tag in suggestions {
+    ///     println!("{}: {} posts", tag.name, tag.post_count.unwrap_or(0));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn autocomplete(query: &str, limit: u32) -> Result<Vec<TagSuggestion>> {
+        let response = shared_client()
+            .get(format!("{}/autocomplete.json", Self::URL))
+            .headers(get_headers())
+            .query(&[
+                ("search[query]", query),
+                ("search[type]", "tag_query"),
+                ("limit", &limit.to_string()),
+            ])
+            .send()
+            .await?;
+        let response = error_for_status(response)
+            .await?
+            .json::<Vec<DanbooruAutocompleteItem>>()
+            .await?;
+
+        Ok(response
+            .into_iter()
+            .map(|item| TagSuggestion {
+                name: item.value,
+                label: item.label,
+                post_count: item.post_count,
+                category: item.category,
+            })
+            .collect())
+    }
+}
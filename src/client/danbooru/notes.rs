@@ -0,0 +1,121 @@
+//! Danbooru notes (`/notes.json`).
+
+use super::{DanbooruClient, get_json};
+use crate::client::ClientBuilder;
+use crate::error::Result;
+use crate::model::danbooru::DanbooruNote;
+use crate::stream::paginated_query;
+
+impl DanbooruClient {
+    /// Starts a search of Danbooru's notes.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::danbooru::DanbooruClient;
+    /// use booru_rs::client::Client;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// let client = DanbooruClient::builder().build();
+    /// let post = client.get_by_id(12345).await?;
+    ///
+    /// if post.has_notes() {
+    ///     let notes = client.notes().post_id(post.id).active(true).get().await?;
+    ///
+    ///     for note in notes {
+    ///         println!("({}, {}) {}x{}: {}", note.x, note.y, note.width, note.height, note.body);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn notes(&self) -> DanbooruNoteQuery {
+        DanbooruNoteQuery::new(self.0.clone())
+    }
+}
+
+/// A search of Danbooru's notes.
+///
+/// Created by [`DanbooruClient::notes`].
+#[derive(Debug, Clone)]
+pub struct DanbooruNoteQuery {
+    builder: ClientBuilder<DanbooruClient>,
+    post_id: Option<u32>,
+    body_matches: Option<String>,
+    is_active: Option<bool>,
+    limit: u32,
+    page: u32,
+}
+
+impl DanbooruNoteQuery {
+    fn new(builder: ClientBuilder<DanbooruClient>) -> Self {
+        Self {
+            builder,
+            post_id: None,
+            body_matches: None,
+            is_active: None,
+            limit: 100,
+            page: 1,
+        }
+    }
+
+    /// Only returns notes on the given post.
+    #[must_use]
+    pub fn post_id(mut self, post_id: u32) -> Self {
+        self.post_id = Some(post_id);
+        self
+    }
+
+    /// Only returns notes whose text matches a pattern.
+    ///
+    /// `*` matches any sequence of characters.
+    #[must_use]
+    pub fn body_matches(mut self, pattern: impl Into<String>) -> Self {
+        self.body_matches = Some(pattern.into());
+        self
+    }
+
+    /// Only returns active (`true`) or deleted (`false`) notes.
+    #[must_use]
+    pub fn active(mut self, active: bool) -> Self {
+        self.is_active = Some(active);
+        self
+    }
+
+    /// Fetches the configured page of notes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or if the response cannot be parsed.
+    pub async fn get(&self) -> Result<Vec<DanbooruNote>> {
+        get_json(&self.builder, "notes.json", &self.params()).await
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![
+            ("limit", self.limit.to_string()),
+            ("page", self.page.to_string()),
+        ];
+
+        if let Some(post_id) = self.post_id {
+            params.push(("search[post_id]", post_id.to_string()));
+        }
+        if let Some(pattern) = &self.body_matches {
+            params.push(("search[body_matches]", pattern.clone()));
+        }
+        if let Some(active) = self.is_active {
+            params.push(("search[is_active]", active.to_string()));
+        }
+
+        params
+    }
+}
+
+paginated_query! {
+    DanbooruNoteQuery => DanbooruNote,
+    noun: "notes",
+    stream: into_note_stream,
+    limit: "Default is 100.",
+    page: "Danbooru page numbering starts at 1, which is the default.",
+}
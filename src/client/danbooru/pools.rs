@@ -0,0 +1,190 @@
+//! Danbooru pools (`/pools.json`).
+
+use super::{DanbooruClient, get_json};
+use crate::client::{Client, ClientBuilder};
+use crate::error::Result;
+use crate::model::danbooru::{DanbooruPool, DanbooruPoolCategory, DanbooruPoolOrder, DanbooruPost};
+use crate::stream::paginated_query;
+
+impl DanbooruClient {
+    /// Retrieves a single pool by its ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::ApiError`] with status 404 if no pool exists
+    /// with the given ID (see [`BooruError::is_not_found`]).
+    /// Returns other errors if the request fails or the response cannot be parsed.
+    ///
+    /// [`BooruError::ApiError`]: crate::error::BooruError::ApiError
+    /// [`BooruError::is_not_found`]: crate::error::BooruError::is_not_found
+    pub async fn pool(&self, id: u32) -> Result<DanbooruPool> {
+        get_json(&self.0, &format!("pools/{id}.json"), &[]).await
+    }
+
+    /// Starts a search of Danbooru's pools.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::danbooru::{DanbooruClient, DanbooruPoolCategory};
+    /// use booru_rs::client::Client;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// let pools = DanbooruClient::builder()
+    ///     .build()
+    ///     .pools()
+    ///     .name_matches("*touhou*")
+    ///     .category(DanbooruPoolCategory::Series)
+    ///     .get()
+    ///     .await?;
+    ///
+    /// for pool in pools {
+    ///     println!("{} ({} posts)", pool.display_name(), pool.post_count);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn pools(&self) -> DanbooruPoolQuery {
+        DanbooruPoolQuery::new(self.0.clone())
+    }
+
+    /// Retrieves the posts of a pool in pool order.
+    ///
+    /// Posts are fetched with [`get_by_ids`](Client::get_by_ids) and returned
+    /// in the order of [`DanbooruPool::post_ids`]. Posts that are no longer
+    /// visible (e.g. deleted or restricted) are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails or if a response cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::danbooru::DanbooruClient;
+    /// use booru_rs::client::Client;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// let client = DanbooruClient::builder().build();
+    /// let pool = client.pool(1234).await?;
+    ///
+    /// for (page, post) in client.pool_posts(&pool).await?.iter().enumerate() {
+    ///     println!("Page {}: #{}", page + 1, post.id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn pool_posts(&self, pool: &DanbooruPool) -> Result<Vec<DanbooruPost>> {
+        Ok(self.get_by_ids(&pool.post_ids).await?.posts)
+    }
+}
+
+/// A search of Danbooru's pools.
+///
+/// Created by [`DanbooruClient::pools`].
+#[derive(Debug, Clone)]
+pub struct DanbooruPoolQuery {
+    builder: ClientBuilder<DanbooruClient>,
+    name_matches: Option<String>,
+    description_matches: Option<String>,
+    category: Option<DanbooruPoolCategory>,
+    is_deleted: Option<bool>,
+    order: Option<DanbooruPoolOrder>,
+    limit: u32,
+    page: u32,
+}
+
+impl DanbooruPoolQuery {
+    fn new(builder: ClientBuilder<DanbooruClient>) -> Self {
+        Self {
+            builder,
+            name_matches: None,
+            description_matches: None,
+            category: None,
+            is_deleted: None,
+            order: None,
+            limit: 100,
+            page: 1,
+        }
+    }
+
+    /// Only returns pools whose name matches a pattern.
+    ///
+    /// `*` matches any sequence of characters.
+    #[must_use]
+    pub fn name_matches(mut self, pattern: impl Into<String>) -> Self {
+        self.name_matches = Some(pattern.into());
+        self
+    }
+
+    /// Only returns pools whose description matches a pattern.
+    #[must_use]
+    pub fn description_matches(mut self, pattern: impl Into<String>) -> Self {
+        self.description_matches = Some(pattern.into());
+        self
+    }
+
+    /// Only returns pools in the given category.
+    #[must_use]
+    pub fn category(mut self, category: DanbooruPoolCategory) -> Self {
+        self.category = Some(category);
+        self
+    }
+
+    /// Only returns deleted (`true`) or non-deleted (`false`) pools.
+    #[must_use]
+    pub fn deleted(mut self, deleted: bool) -> Self {
+        self.is_deleted = Some(deleted);
+        self
+    }
+
+    /// Sets the sort order of the results.
+    #[must_use]
+    pub fn order(mut self, order: DanbooruPoolOrder) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    /// Fetches the configured page of pools.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or if the response cannot be parsed.
+    pub async fn get(&self) -> Result<Vec<DanbooruPool>> {
+        get_json(&self.builder, "pools.json", &self.params()).await
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![
+            ("limit", self.limit.to_string()),
+            ("page", self.page.to_string()),
+        ];
+
+        if let Some(pattern) = &self.name_matches {
+            params.push(("search[name_matches]", pattern.clone()));
+        }
+        if let Some(pattern) = &self.description_matches {
+            params.push(("search[description_matches]", pattern.clone()));
+        }
+        if let Some(category) = self.category {
+            params.push(("search[category]", category.to_string()));
+        }
+        if let Some(deleted) = self.is_deleted {
+            params.push(("search[is_deleted]", deleted.to_string()));
+        }
+        if let Some(order) = self.order {
+            params.push(("search[order]", order.to_string()));
+        }
+
+        params
+    }
+}
+
+paginated_query! {
+    DanbooruPoolQuery => DanbooruPool,
+    noun: "pools",
+    stream: into_pool_stream,
+    limit: "Default is 100.",
+    page: "Danbooru page numbering starts at 1, which is the default.",
+}
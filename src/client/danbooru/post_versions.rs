@@ -0,0 +1,157 @@
+//! Danbooru post version history (`/post_versions.json`).
+
+use super::{DanbooruClient, get_json};
+use crate::client::ClientBuilder;
+use crate::error::Result;
+use crate::model::danbooru::{DanbooruPostHistory, DanbooruPostVersion};
+use crate::stream::paginated_query;
+
+impl DanbooruClient {
+    /// Starts a search of Danbooru's post versions.
+    ///
+    /// Versions are returned newest first.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::danbooru::DanbooruClient;
+    /// use booru_rs::client::Client;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// let versions = DanbooruClient::builder()
+    ///     .build()
+    ///     .post_versions()
+    ///     .updater_name("some_user")
+    ///     .limit(20)
+    ///     .get()
+    ///     .await?;
+    ///
+    /// for version in versions {
+    ///     println!(
+    ///         "#{} v{}: +{:?} -{:?}",
+    ///         version.post_id, version.version, version.added_tags, version.removed_tags
+    ///     );
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn post_versions(&self) -> DanbooruPostVersionQuery {
+        DanbooruPostVersionQuery::new(self.0.clone())
+    }
+
+    /// Retrieves every version of a post.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails or if a response cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::{Duration, SystemTime};
+    /// use booru_rs::danbooru::DanbooruClient;
+    /// use booru_rs::client::Client;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// let history = DanbooruClient::builder().build().post_history(12345).await?;
+    ///
+    /// let a_week_ago = SystemTime::now() - Duration::from_secs(7 * 24 * 60 * 60);
+    /// println!("Tags a week ago: {:?}", history.tags_at(a_week_ago));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn post_history(&self, post_id: u32) -> Result<DanbooruPostHistory> {
+        let versions = self
+            .post_versions()
+            .post_id(post_id)
+            .into_version_stream()
+            .collect()
+            .await?;
+
+        Ok(DanbooruPostHistory::new(versions))
+    }
+}
+
+/// A search of Danbooru's post versions.
+///
+/// Created by [`DanbooruClient::post_versions`].
+#[derive(Debug, Clone)]
+pub struct DanbooruPostVersionQuery {
+    builder: ClientBuilder<DanbooruClient>,
+    post_id: Option<u32>,
+    updater_id: Option<u32>,
+    updater_name: Option<String>,
+    limit: u32,
+    page: u32,
+}
+
+impl DanbooruPostVersionQuery {
+    fn new(builder: ClientBuilder<DanbooruClient>) -> Self {
+        Self {
+            builder,
+            post_id: None,
+            updater_id: None,
+            updater_name: None,
+            limit: 100,
+            page: 1,
+        }
+    }
+
+    /// Only returns versions of the given post.
+    #[must_use]
+    pub fn post_id(mut self, post_id: u32) -> Self {
+        self.post_id = Some(post_id);
+        self
+    }
+
+    /// Only returns edits made by the user with this ID.
+    #[must_use]
+    pub fn updater_id(mut self, updater_id: u32) -> Self {
+        self.updater_id = Some(updater_id);
+        self
+    }
+
+    /// Only returns edits made by the user with this name.
+    #[must_use]
+    pub fn updater_name(mut self, name: impl Into<String>) -> Self {
+        self.updater_name = Some(name.into());
+        self
+    }
+
+    /// Fetches the configured page of versions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or if the response cannot be parsed.
+    pub async fn get(&self) -> Result<Vec<DanbooruPostVersion>> {
+        get_json(&self.builder, "post_versions.json", &self.params()).await
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![
+            ("limit", self.limit.to_string()),
+            ("page", self.page.to_string()),
+        ];
+
+        if let Some(post_id) = self.post_id {
+            params.push(("search[post_id]", post_id.to_string()));
+        }
+        if let Some(updater_id) = self.updater_id {
+            params.push(("search[updater_id]", updater_id.to_string()));
+        }
+        if let Some(name) = &self.updater_name {
+            params.push(("search[updater_name]", name.clone()));
+        }
+
+        params
+    }
+}
+
+paginated_query! {
+    DanbooruPostVersionQuery => DanbooruPostVersion,
+    noun: "versions",
+    stream: into_version_stream,
+    limit: "Default is 100.",
+    page: "Danbooru page numbering starts at 1, which is the default.",
+}
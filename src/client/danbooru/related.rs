@@ -0,0 +1,110 @@
+//! Danbooru related tags (`/related_tag.json`).
+
+use serde::Deserialize;
+
+use super::{DanbooruClient, get_json};
+use crate::client::ClientBuilder;
+use crate::error::Result;
+use crate::model::danbooru::{DanbooruRelatedTag, DanbooruTagCategory};
+
+#[derive(Debug, Deserialize)]
+struct RelatedTagResponse {
+    #[serde(default)]
+    related_tags: Vec<DanbooruRelatedTag>,
+}
+
+impl DanbooruClient {
+    /// Starts a search for tags that frequently appear together with a query.
+    ///
+    /// The query can be a single tag or a full tag search such as
+    /// `"cat_ears rating:g"`. Danbooru samples the matching posts and scores
+    /// each co-occurring tag.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::autocomplete::TagSuggestion;
+    /// use booru_rs::danbooru::{DanbooruClient, DanbooruTagCategory};
+    /// use booru_rs::client::Client;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// let related = DanbooruClient::builder()
+    ///     .build()
+    ///     .related_tags("cat_ears")
+    ///     .category(DanbooruTagCategory::Character)
+    ///     .limit(10)
+    ///     .get()
+    ///     .await?;
+    ///
+    /// for tag in &related {
+    ///     println!("{}: {:.2}", tag.tag.name, tag.cosine_similarity);
+    /// }
+    ///
+    /// let suggestions: Vec<TagSuggestion> = related.into_iter().map(Into::into).collect();
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn related_tags(&self, query: impl Into<String>) -> DanbooruRelatedTagQuery {
+        DanbooruRelatedTagQuery {
+            builder: self.0.clone(),
+            query: query.into(),
+            category: None,
+            limit: None,
+        }
+    }
+}
+
+/// A search for tags related to a query.
+///
+/// Created by [`DanbooruClient::related_tags`].
+#[derive(Debug, Clone)]
+pub struct DanbooruRelatedTagQuery {
+    builder: ClientBuilder<DanbooruClient>,
+    query: String,
+    category: Option<DanbooruTagCategory>,
+    limit: Option<u32>,
+}
+
+impl DanbooruRelatedTagQuery {
+    /// Only returns related tags in the given category.
+    #[must_use]
+    pub fn category(mut self, category: DanbooruTagCategory) -> Self {
+        self.category = Some(category);
+        self
+    }
+
+    /// Sets the maximum number of related tags to return.
+    ///
+    /// Defaults to Danbooru's own limit (currently 25).
+    #[must_use]
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Fetches the related tags, most related first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or if the response cannot be parsed.
+    pub async fn get(&self) -> Result<Vec<DanbooruRelatedTag>> {
+        let response: RelatedTagResponse =
+            get_json(&self.builder, "related_tag.json", &self.params()).await?;
+
+        Ok(response.related_tags)
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![("query", self.query.clone())];
+
+        if let Some(category) = self.category {
+            params.push(("category", category.to_string()));
+        }
+        if let Some(limit) = self.limit {
+            params.push(("limit", limit.to_string()));
+        }
+
+        params
+    }
+}
@@ -0,0 +1,390 @@
+//! Canonicalization of Danbooru tags through aliases and implications.
+
+use std::collections::{HashSet, VecDeque};
+
+use super::{DanbooruAutocompleteItem, DanbooruClient, DanbooruTagRelationQuery, get_json};
+use crate::cache::Cache;
+use crate::client::ClientBuilder;
+use crate::error::Result;
+
+/// Metatag names that are passed through the resolver untouched.
+const METATAGS: &[&str] = &[
+    "age",
+    "approver",
+    "arttags",
+    "chartags",
+    "comm",
+    "commenter",
+    "comment",
+    "comments",
+    "commentary",
+    "copytags",
+    "date",
+    "disapproved",
+    "downvote",
+    "duration",
+    "fav",
+    "favcount",
+    "filesize",
+    "filetype",
+    "flagger",
+    "gentags",
+    "height",
+    "id",
+    "is",
+    "limit",
+    "md5",
+    "metatags",
+    "mpixels",
+    "note",
+    "noter",
+    "notes",
+    "order",
+    "ordfav",
+    "ordpool",
+    "parent",
+    "pixiv",
+    "pixiv_id",
+    "pool",
+    "rating",
+    "ratio",
+    "score",
+    "search",
+    "source",
+    "status",
+    "tagcount",
+    "upvote",
+    "user",
+    "width",
+];
+
+impl DanbooruClient {
+    /// Creates a resolver that canonicalizes tags using this client's settings.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::danbooru::DanbooruClient;
+    /// use booru_rs::client::Client;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// let resolver = DanbooruClient::builder().build().tag_resolver();
+    ///
+    /// // Aliases are replaced, duplicates and implied tags dropped, so the
+    /// // result fits in Danbooru's two tag slots.
+    /// let tags = resolver.resolve(["cat_girl", "animal_ears", "rating:g"]).await?;
+    ///
+    /// let posts = DanbooruClient::builder()
+    ///     .tags(tags)?
+    ///     .build()
+    ///     .get()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn tag_resolver(&self) -> DanbooruTagResolver {
+        DanbooruTagResolver {
+            builder: self.0.clone(),
+            cache: None,
+            correct_typos: false,
+        }
+    }
+}
+
+/// Resolves Danbooru tags to their canonical names.
+///
+/// Created by [`DanbooruClient::tag_resolver`]. The resolver follows active
+/// tag aliases, can expand active tag implications, and drops search terms
+/// that are redundant, so that queries don't waste Danbooru's tag slots.
+///
+/// Search prefixes (`-`, `~`) are kept, and metatags such as `rating:g` or
+/// `order:score` as well as wildcard terms are passed through untouched.
+///
+/// Lookups can be cached with [`with_cache`](Self::with_cache); without a
+/// cache every call queries the API.
+#[derive(Debug, Clone)]
+pub struct DanbooruTagResolver {
+    builder: ClientBuilder<DanbooruClient>,
+    cache: Option<Cache>,
+    correct_typos: bool,
+}
+
+impl DanbooruTagResolver {
+    /// Caches alias, implication and typo lookups in the given cache.
+    ///
+    /// The cache can be shared with other resolvers.
+    #[must_use]
+    pub fn with_cache(mut self, cache: Cache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Replaces tags that don't exist with Danbooru's best autocomplete match.
+    ///
+    /// This costs up to two extra requests per unknown tag.
+    #[must_use]
+    pub fn correct_typos(mut self) -> Self {
+        self.correct_typos = true;
+        self
+    }
+
+    /// Returns the canonical name of a single tag.
+    ///
+    /// The name is normalized (trimmed, lowercased, spaces replaced with
+    /// underscores) and then followed through its active alias, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a lookup request fails.
+    pub async fn canonical_name(&self, tag: &str) -> Result<String> {
+        let name = normalize(tag);
+
+        let name = match self.alias_of(&name).await? {
+            Some(consequent) => consequent,
+            None if self.correct_typos => self.correction_of(&name).await?.unwrap_or(name),
+            None => name,
+        };
+
+        Ok(name)
+    }
+
+    /// Returns the tags directly implied by a tag through active implications.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a lookup request fails.
+    pub async fn implied_tags(&self, tag: &str) -> Result<Vec<String>> {
+        let name = normalize(tag);
+        let key = self.cache_key("implications", &name);
+
+        if let Some(cache) = &self.cache
+            && let Some(implied) = cache.get::<Vec<String>>(&key).await
+        {
+            return Ok(implied);
+        }
+
+        let implied: Vec<String> = self
+            .relations("tag_implications.json")
+            .antecedent_name(&name)
+            .get()
+            .await?
+            .into_iter()
+            .map(|implication| implication.consequent_name)
+            .collect();
+
+        if let Some(cache) = &self.cache {
+            cache.insert(key, &implied).await;
+        }
+
+        Ok(implied)
+    }
+
+    /// Canonicalizes a list of search terms for use with
+    /// [`ClientBuilder::tags`].
+    ///
+    /// Each tag is replaced by its canonical name, duplicates are removed,
+    /// and included tags that are implied by another included tag are
+    /// dropped, since searching for them doesn't narrow the results.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a lookup request fails.
+    pub async fn resolve<I, S>(&self, tags: I) -> Result<Vec<String>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let terms = self.canonicalize(tags).await?;
+
+        let mut implied = HashSet::new();
+        for term in &terms {
+            if let Some(("", name)) = split_term(term) {
+                implied.extend(self.implication_closure(name).await?);
+            }
+        }
+
+        Ok(terms
+            .into_iter()
+            .filter(|term| !matches!(split_term(term), Some(("", name)) if implied.contains(name)))
+            .collect())
+    }
+
+    /// Canonicalizes a list of search terms and adds every tag they imply.
+    ///
+    /// Implications are followed transitively. Implied tags are appended
+    /// after the given terms.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a lookup request fails.
+    pub async fn expand<I, S>(&self, tags: I) -> Result<Vec<String>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut terms = self.canonicalize(tags).await?;
+        let mut seen: HashSet<String> = terms.iter().cloned().collect();
+
+        for index in 0..terms.len() {
+            let Some(("", name)) = split_term(&terms[index]) else {
+                continue;
+            };
+            for tag in self.implication_closure(name).await? {
+                if seen.insert(tag.clone()) {
+                    terms.push(tag);
+                }
+            }
+        }
+
+        Ok(terms)
+    }
+
+    /// Canonicalizes each term and removes duplicates, keeping the order.
+    async fn canonicalize<I, S>(&self, tags: I) -> Result<Vec<String>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut terms = Vec::new();
+        let mut seen = HashSet::new();
+
+        for tag in tags {
+            let tag = tag.as_ref().trim();
+            let term = match split_term(tag) {
+                Some((prefix, name)) => format!("{prefix}{}", self.canonical_name(name).await?),
+                None => tag.to_string(),
+            };
+            if !term.is_empty() && seen.insert(term.clone()) {
+                terms.push(term);
+            }
+        }
+
+        Ok(terms)
+    }
+
+    /// Returns every tag transitively implied by a tag, nearest first.
+    async fn implication_closure(&self, tag: &str) -> Result<Vec<String>> {
+        let mut closure = Vec::new();
+        let mut seen = HashSet::from([tag.to_string()]);
+        let mut queue = VecDeque::from([tag.to_string()]);
+
+        while let Some(current) = queue.pop_front() {
+            for implied in self.implied_tags(&current).await? {
+                if seen.insert(implied.clone()) {
+                    closure.push(implied.clone());
+                    queue.push_back(implied);
+                }
+            }
+        }
+
+        Ok(closure)
+    }
+
+    async fn alias_of(&self, name: &str) -> Result<Option<String>> {
+        let key = self.cache_key("alias", name);
+
+        if let Some(cache) = &self.cache
+            && let Some(alias) = cache.get::<Option<String>>(&key).await
+        {
+            return Ok(alias);
+        }
+
+        let alias = self
+            .relations("tag_aliases.json")
+            .antecedent_name(name)
+            .limit(1)
+            .get()
+            .await?
+            .into_iter()
+            .next()
+            .map(|alias| alias.consequent_name);
+
+        if let Some(cache) = &self.cache {
+            cache.insert(key, &alias).await;
+        }
+
+        Ok(alias)
+    }
+
+    async fn correction_of(&self, name: &str) -> Result<Option<String>> {
+        let key = self.cache_key("correction", name);
+
+        if let Some(cache) = &self.cache
+            && let Some(correction) = cache.get::<Option<String>>(&key).await
+        {
+            return Ok(correction);
+        }
+
+        let exists = !DanbooruClient(self.builder.clone())
+            .tags()
+            .names([name])
+            .hide_empty()
+            .limit(1)
+            .get()
+            .await?
+            .is_empty();
+
+        let correction = if exists {
+            None
+        } else {
+            let suggestions: Vec<DanbooruAutocompleteItem> = get_json(
+                &self.builder,
+                "autocomplete.json",
+                &[
+                    ("search[query]", name.to_string()),
+                    ("search[type]", "tag_query".to_string()),
+                    ("limit", "1".to_string()),
+                ],
+            )
+            .await?;
+            suggestions.into_iter().next().map(|item| item.value)
+        };
+
+        if let Some(cache) = &self.cache {
+            cache.insert(key, &correction).await;
+        }
+
+        Ok(correction)
+    }
+
+    /// Returns the cache key of a lookup.
+    ///
+    /// Keys include the API host, so a cache shared with resolvers for
+    /// mirrors or test servers never mixes up their answers.
+    fn cache_key(&self, kind: &str, name: &str) -> String {
+        let url = &self.builder.url;
+        let host = reqwest::Url::parse(url)
+            .map(|url| crate::circuit::host_key(&url))
+            .unwrap_or_else(|_| url.clone());
+
+        format!("danbooru:{host}:{kind}:{name}")
+    }
+
+    fn relations(&self, endpoint: &'static str) -> DanbooruTagRelationQuery {
+        DanbooruTagRelationQuery::new(self.builder.clone(), endpoint).active()
+    }
+}
+
+/// Normalizes a tag name the way Danbooru stores it.
+fn normalize(tag: &str) -> String {
+    tag.trim().to_lowercase().replace(' ', "_")
+}
+
+/// Splits a search term into its prefix (`-`, `~` or empty) and tag name.
+///
+/// Returns `None` for metatags and wildcard searches, which can't be resolved.
+fn split_term(term: &str) -> Option<(&str, &str)> {
+    let name = term.trim_start_matches(['-', '~']);
+    let prefix = &term[..term.len() - name.len()];
+
+    let is_metatag = name
+        .split_once(':')
+        .is_some_and(|(metatag, _)| METATAGS.contains(&metatag.to_lowercase().as_str()));
+
+    if name.is_empty() || is_metatag || name.contains('*') {
+        None
+    } else {
+        Some((prefix, name))
+    }
+}
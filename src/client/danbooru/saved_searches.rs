@@ -0,0 +1,242 @@
+//! Danbooru saved searches (`/saved_searches.json`).
+
+use reqwest::Method;
+
+use super::{DanbooruClient, get_json, request, require_credentials};
+use crate::client::ClientBuilder;
+use crate::error::Result;
+use crate::model::danbooru::DanbooruSavedSearch;
+use crate::stream::{PostStream, paginated_query};
+
+impl DanbooruClient {
+    /// Starts a search of the authenticated user's saved searches.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::danbooru::DanbooruClient;
+    /// use booru_rs::client::Client;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// let client = DanbooruClient::builder()
+    ///     .set_credentials("your_api_key", "your_login")
+    ///     .build();
+    ///
+    /// for saved in client.saved_searches().label("daily").get().await? {
+    ///     let posts = client.saved_search_builder(&saved).limit(20).build().get().await?;
+    ///     println!("{}: {} new posts", saved.query, posts.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn saved_searches(&self) -> DanbooruSavedSearchQuery {
+        DanbooruSavedSearchQuery::new(self.0.clone())
+    }
+
+    /// Creates a saved search with the given labels.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::Unauthorized`] if no credentials are set or
+    /// they are invalid, and other errors if the request fails or the
+    /// response cannot be parsed.
+    ///
+    /// [`BooruError::Unauthorized`]: crate::error::BooruError::Unauthorized
+    pub async fn create_saved_search<I, S>(
+        &self,
+        query: &str,
+        labels: I,
+    ) -> Result<DanbooruSavedSearch>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let builder = &self.0;
+        require_credentials(builder)?;
+
+        let saved_search = builder
+            .send(
+                request(builder, Method::POST, "saved_searches.json")
+                    .form(&saved_search_params(query, labels)),
+            )
+            .await?
+            .json::<DanbooruSavedSearch>()
+            .await?;
+
+        Ok(saved_search)
+    }
+
+    /// Replaces the query and labels of a saved search.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::Unauthorized`] if no credentials are set or
+    /// they are invalid, and other errors if the request fails.
+    ///
+    /// [`BooruError::Unauthorized`]: crate::error::BooruError::Unauthorized
+    pub async fn update_saved_search<I, S>(&self, id: u32, query: &str, labels: I) -> Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let builder = &self.0;
+        require_credentials(builder)?;
+
+        builder
+            .send(
+                request(builder, Method::PUT, &format!("saved_searches/{id}.json"))
+                    .form(&saved_search_params(query, labels)),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Deletes a saved search.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::Unauthorized`] if no credentials are set or
+    /// they are invalid, and other errors if the request fails.
+    ///
+    /// [`BooruError::Unauthorized`]: crate::error::BooruError::Unauthorized
+    pub async fn delete_saved_search(&self, id: u32) -> Result<()> {
+        let builder = &self.0;
+        require_credentials(builder)?;
+
+        builder
+            .send(request(
+                builder,
+                Method::DELETE,
+                &format!("saved_searches/{id}.json"),
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns a post query for a saved search.
+    ///
+    /// The query keeps this client's settings (URL, credentials, limit,
+    /// rate limiter, ...) but replaces its tags with the saved search's.
+    /// Use [`ClientBuilder::into_post_stream`] on the result to stream every
+    /// matching post.
+    ///
+    /// The terms are not checked against [`Client::MAX_TAGS`], the
+    /// anonymous limit: Danbooru applies the tag limit of the account whose
+    /// credentials are set.
+    ///
+    /// [`Client::MAX_TAGS`]: crate::client::Client::MAX_TAGS
+    #[must_use]
+    pub fn saved_search_builder(&self, saved_search: &DanbooruSavedSearch) -> ClientBuilder<Self> {
+        let mut builder = self.0.clone();
+        builder.tags = saved_search.terms().map(str::to_string).collect();
+
+        builder
+    }
+
+    /// Returns a stream of every post matching a saved search.
+    ///
+    /// Shorthand for [`saved_search_builder`](Self::saved_search_builder)
+    /// followed by [`ClientBuilder::into_post_stream`].
+    #[must_use]
+    pub fn saved_search_stream(&self, saved_search: &DanbooruSavedSearch) -> PostStream<Self> {
+        self.saved_search_builder(saved_search).into_post_stream()
+    }
+}
+
+fn saved_search_params<I, S>(query: &str, labels: I) -> Vec<(&'static str, String)>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let labels: Vec<String> = labels
+        .into_iter()
+        .map(|label| label.as_ref().to_string())
+        .collect();
+
+    vec![
+        ("saved_search[query]", query.to_string()),
+        ("saved_search[label_string]", labels.join(" ")),
+    ]
+}
+
+/// A search of the authenticated user's saved searches.
+///
+/// Created by [`DanbooruClient::saved_searches`].
+#[derive(Debug, Clone)]
+pub struct DanbooruSavedSearchQuery {
+    builder: ClientBuilder<DanbooruClient>,
+    label: Option<String>,
+    query_matches: Option<String>,
+    limit: u32,
+    page: u32,
+}
+
+impl DanbooruSavedSearchQuery {
+    fn new(builder: ClientBuilder<DanbooruClient>) -> Self {
+        Self {
+            builder,
+            label: None,
+            query_matches: None,
+            limit: 100,
+            page: 1,
+        }
+    }
+
+    /// Only returns saved searches with the given label.
+    #[must_use]
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Only returns saved searches whose query matches a pattern.
+    ///
+    /// `*` matches any sequence of characters.
+    #[must_use]
+    pub fn query_matches(mut self, pattern: impl Into<String>) -> Self {
+        self.query_matches = Some(pattern.into());
+        self
+    }
+
+    /// Fetches the configured page of saved searches.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::Unauthorized`] if no credentials are set or
+    /// they are invalid, and other errors if the request fails or the
+    /// response cannot be parsed.
+    ///
+    /// [`BooruError::Unauthorized`]: crate::error::BooruError::Unauthorized
+    pub async fn get(&self) -> Result<Vec<DanbooruSavedSearch>> {
+        require_credentials(&self.builder)?;
+
+        get_json(&self.builder, "saved_searches.json", &self.params()).await
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![
+            ("limit", self.limit.to_string()),
+            ("page", self.page.to_string()),
+        ];
+
+        if let Some(label) = &self.label {
+            params.push(("search[label]", label.clone()));
+        }
+        if let Some(pattern) = &self.query_matches {
+            params.push(("search[query_like]", pattern.clone()));
+        }
+
+        params
+    }
+}
+
+paginated_query! {
+    DanbooruSavedSearchQuery => DanbooruSavedSearch,
+    noun: "saved searches",
+    stream: into_saved_search_stream,
+    limit: "Default is 100.",
+    page: "Danbooru page numbering starts at 1, which is the default.",
+}
@@ -0,0 +1,312 @@
+//! Danbooru tag search (`/tags.json`).
+
+use super::{DanbooruClient, get_json};
+use crate::client::ClientBuilder;
+use crate::error::Result;
+use crate::model::danbooru::{
+    DanbooruTag, DanbooruTagCategory, DanbooruTagOrder, DanbooruTagRelation,
+};
+use crate::stream::paginated_query;
+
+impl DanbooruClient {
+    /// Starts a search of Danbooru's tags.
+    ///
+    /// The query reuses this client's base URL, HTTP client, rate limiter
+    /// and circuit breaker.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::danbooru::{DanbooruClient, DanbooruTagCategory, DanbooruTagOrder};
+    /// use booru_rs::client::Client;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// let tags = DanbooruClient::builder()
+    ///     .build()
+    ///     .tags()
+    ///     .name_matches("cat_*")
+    ///     .category(DanbooruTagCategory::General)
+    ///     .order(DanbooruTagOrder::Count)
+    ///     .limit(20)
+    ///     .get()
+    ///     .await?;
+    ///
+    /// for tag in tags {
+    ///     println!("{}: {} posts", tag.name, tag.post_count);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn tags(&self) -> DanbooruTagQuery {
+        DanbooruTagQuery::new(self.0.clone())
+    }
+
+    /// Starts a search of Danbooru's tag aliases (`/tag_aliases.json`).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::danbooru::DanbooruClient;
+    /// use booru_rs::client::Client;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// let aliases = DanbooruClient::builder()
+    ///     .build()
+    ///     .tag_aliases()
+    ///     .antecedent_name("cat_girl")
+    ///     .active()
+    ///     .get()
+    ///     .await?;
+    ///
+    /// for alias in aliases {
+    ///     println!("{} -> {}", alias.antecedent_name, alias.consequent_name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn tag_aliases(&self) -> DanbooruTagRelationQuery {
+        DanbooruTagRelationQuery::new(self.0.clone(), "tag_aliases.json")
+    }
+
+    /// Starts a search of Danbooru's tag implications (`/tag_implications.json`).
+    #[must_use]
+    pub fn tag_implications(&self) -> DanbooruTagRelationQuery {
+        DanbooruTagRelationQuery::new(self.0.clone(), "tag_implications.json")
+    }
+}
+
+/// A search of Danbooru's tags.
+///
+/// Created by [`DanbooruClient::tags`]. Results can be fetched one page at
+/// a time with [`get`](Self::get), or streamed with
+/// [`into_page_stream`](Self::into_page_stream) and
+/// [`into_tag_stream`](Self::into_tag_stream).
+#[derive(Debug, Clone)]
+pub struct DanbooruTagQuery {
+    builder: ClientBuilder<DanbooruClient>,
+    name_matches: Option<String>,
+    names: Vec<String>,
+    category: Option<DanbooruTagCategory>,
+    order: Option<DanbooruTagOrder>,
+    hide_empty: bool,
+    is_deprecated: Option<bool>,
+    limit: u32,
+    page: u32,
+}
+
+impl DanbooruTagQuery {
+    fn new(builder: ClientBuilder<DanbooruClient>) -> Self {
+        Self {
+            builder,
+            name_matches: None,
+            names: Vec::new(),
+            category: None,
+            order: None,
+            hide_empty: false,
+            is_deprecated: None,
+            limit: 100,
+            page: 1,
+        }
+    }
+
+    /// Only returns tags whose name matches a pattern.
+    ///
+    /// `*` matches any sequence of characters, e.g. `"cat_*"`.
+    #[must_use]
+    pub fn name_matches(mut self, pattern: impl Into<String>) -> Self {
+        self.name_matches = Some(pattern.into());
+        self
+    }
+
+    /// Only returns the tags with exactly these names.
+    #[must_use]
+    pub fn names<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.names.extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// Only returns tags in the given category.
+    #[must_use]
+    pub fn category(mut self, category: DanbooruTagCategory) -> Self {
+        self.category = Some(category);
+        self
+    }
+
+    /// Sets the sort order of the results.
+    #[must_use]
+    pub fn order(mut self, order: DanbooruTagOrder) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    /// Excludes tags that have no posts.
+    #[must_use]
+    pub fn hide_empty(mut self) -> Self {
+        self.hide_empty = true;
+        self
+    }
+
+    /// Only returns deprecated (`true`) or non-deprecated (`false`) tags.
+    #[must_use]
+    pub fn deprecated(mut self, deprecated: bool) -> Self {
+        self.is_deprecated = Some(deprecated);
+        self
+    }
+
+    /// Fetches the configured page of tags.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or if the response cannot be parsed.
+    pub async fn get(&self) -> Result<Vec<DanbooruTag>> {
+        get_json(&self.builder, "tags.json", &self.params()).await
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![
+            ("limit", self.limit.to_string()),
+            ("page", self.page.to_string()),
+        ];
+
+        if let Some(pattern) = &self.name_matches {
+            params.push(("search[name_matches]", pattern.clone()));
+        }
+        if !self.names.is_empty() {
+            params.push(("search[name_comma]", self.names.join(",")));
+        }
+        if let Some(category) = self.category {
+            params.push(("search[category]", category.id().to_string()));
+        }
+        if let Some(order) = self.order {
+            params.push(("search[order]", order.to_string()));
+        }
+        if self.hide_empty {
+            params.push(("search[hide_empty]", "true".to_string()));
+        }
+        if let Some(deprecated) = self.is_deprecated {
+            params.push(("search[is_deprecated]", deprecated.to_string()));
+        }
+
+        params
+    }
+}
+
+paginated_query! {
+    DanbooruTagQuery => DanbooruTag,
+    noun: "tags",
+    stream: into_tag_stream,
+    limit: "Default is 100. Danbooru allows up to 1000.",
+    page: "Danbooru page numbering starts at 1, which is the default.",
+}
+
+/// A search of Danbooru's tag aliases or implications.
+///
+/// Created by [`DanbooruClient::tag_aliases`] or
+/// [`DanbooruClient::tag_implications`].
+#[derive(Debug, Clone)]
+pub struct DanbooruTagRelationQuery {
+    builder: ClientBuilder<DanbooruClient>,
+    endpoint: &'static str,
+    antecedent_name: Option<String>,
+    consequent_name: Option<String>,
+    name_matches: Option<String>,
+    status: Option<String>,
+    limit: u32,
+    page: u32,
+}
+
+impl DanbooruTagRelationQuery {
+    pub(super) fn new(builder: ClientBuilder<DanbooruClient>, endpoint: &'static str) -> Self {
+        Self {
+            builder,
+            endpoint,
+            antecedent_name: None,
+            consequent_name: None,
+            name_matches: None,
+            status: None,
+            limit: 100,
+            page: 1,
+        }
+    }
+
+    /// Only returns relations whose antecedent is exactly this tag.
+    #[must_use]
+    pub fn antecedent_name(mut self, name: impl Into<String>) -> Self {
+        self.antecedent_name = Some(name.into());
+        self
+    }
+
+    /// Only returns relations whose consequent is exactly this tag.
+    #[must_use]
+    pub fn consequent_name(mut self, name: impl Into<String>) -> Self {
+        self.consequent_name = Some(name.into());
+        self
+    }
+
+    /// Only returns relations where either tag matches a pattern.
+    ///
+    /// `*` matches any sequence of characters.
+    #[must_use]
+    pub fn name_matches(mut self, pattern: impl Into<String>) -> Self {
+        self.name_matches = Some(pattern.into());
+        self
+    }
+
+    /// Only returns relations with the given status (e.g. `"active"`).
+    #[must_use]
+    pub fn status(mut self, status: impl Into<String>) -> Self {
+        self.status = Some(status.into());
+        self
+    }
+
+    /// Only returns relations that are currently in effect.
+    #[must_use]
+    pub fn active(self) -> Self {
+        self.status("active")
+    }
+
+    /// Fetches the configured page of relations.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or if the response cannot be parsed.
+    pub async fn get(&self) -> Result<Vec<DanbooruTagRelation>> {
+        get_json(&self.builder, self.endpoint, &self.params()).await
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![
+            ("limit", self.limit.to_string()),
+            ("page", self.page.to_string()),
+        ];
+
+        if let Some(name) = &self.antecedent_name {
+            params.push(("search[antecedent_name]", name.clone()));
+        }
+        if let Some(name) = &self.consequent_name {
+            params.push(("search[consequent_name]", name.clone()));
+        }
+        if let Some(pattern) = &self.name_matches {
+            params.push(("search[name_matches]", pattern.clone()));
+        }
+        if let Some(status) = &self.status {
+            params.push(("search[status]", status.clone()));
+        }
+
+        params
+    }
+}
+
+paginated_query! {
+    DanbooruTagRelationQuery => DanbooruTagRelation,
+    noun: "relations",
+    stream: into_relation_stream,
+    limit: "Default is 100.",
+    page: "Danbooru page numbering starts at 1, which is the default.",
+}
@@ -0,0 +1,278 @@
+//! Danbooru uploads (`/uploads.json`) and post creation.
+
+use std::path::Path;
+use std::time::Duration;
+
+use reqwest::Method;
+use reqwest::multipart::{Form, Part};
+
+use super::{DanbooruClient, get_json, rating_code, request, require_credentials};
+use crate::error::{BooruError, Result};
+use crate::model::danbooru::{DanbooruPost, DanbooruRating, DanbooruUpload};
+
+/// Fields requested for uploads, so that their media assets are included.
+const UPLOAD_FIELDS: &str = "id,source,status,error,media_asset_count,uploader_id,created_at,updated_at,upload_media_assets";
+
+/// Maximum number of status checks made by [`DanbooruClient::wait_for_upload`].
+const MAX_UPLOAD_POLLS: u32 = 60;
+
+impl DanbooruClient {
+    /// Uploads a local file.
+    ///
+    /// The upload is processed in the background; use
+    /// [`wait_for_upload`](Self::wait_for_upload) to wait for it and then
+    /// [`create_post`](Self::create_post) to publish it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::Unauthorized`] if no credentials are set or
+    /// they are invalid, [`BooruError::Io`] if the file cannot be read, and
+    /// other errors if the request fails or the response cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    /// use std::time::Duration;
+    /// use booru_rs::danbooru::{DanbooruClient, DanbooruNewPost, DanbooruRating};
+    /// use booru_rs::client::Client;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// let client = DanbooruClient::builder()
+    ///     .set_credentials("your_api_key", "your_login")
+    ///     .build();
+    ///
+    /// let upload = client.upload_file(Path::new("artwork.png")).await?;
+    /// let upload = client.wait_for_upload(upload.id, Duration::from_secs(1)).await?;
+    ///
+    /// if let Some(asset) = upload.upload_media_assets.first() {
+    ///     let post = client
+    ///         .create_post(
+    ///             &DanbooruNewPost::new(asset.id, ["1girl", "cat_ears"])
+    ///                 .rating(DanbooruRating::General)
+    ///                 .source("https://example.com/artwork"),
+    ///         )
+    ///         .await?;
+    ///     println!("Created post #{}", post.id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`BooruError::Unauthorized`]: crate::error::BooruError::Unauthorized
+    /// [`BooruError::Io`]: crate::error::BooruError::Io
+    pub async fn upload_file(&self, path: &Path) -> Result<DanbooruUpload> {
+        require_credentials(&self.0)?;
+
+        let file = tokio::fs::File::open(path).await?;
+        let length = file.metadata().await?.len();
+        let filename = path.file_name().map_or_else(
+            || "upload".into(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+
+        // The file is streamed from disk rather than read into memory.
+        let form = Form::new().part(
+            "upload[files][0]",
+            Part::stream_with_length(file, length).file_name(filename),
+        );
+
+        let response = self
+            .0
+            .send(request(&self.0, Method::POST, "uploads.json").multipart(form))
+            .await?;
+
+        Ok(response.json().await?)
+    }
+
+    /// Uploads the files found at a source URL, such as a Pixiv or Twitter
+    /// post.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::Unauthorized`] if no credentials are set or
+    /// they are invalid, and other errors if the request fails or the
+    /// response cannot be parsed.
+    ///
+    /// [`BooruError::Unauthorized`]: crate::error::BooruError::Unauthorized
+    pub async fn upload_source(&self, source: &str) -> Result<DanbooruUpload> {
+        require_credentials(&self.0)?;
+
+        let response = self
+            .0
+            .send(
+                request(&self.0, Method::POST, "uploads.json").form(&[("upload[source]", source)]),
+            )
+            .await?;
+
+        Ok(response.json().await?)
+    }
+
+    /// Retrieves an upload, including its media assets.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or if the response cannot be parsed.
+    pub async fn upload(&self, id: u32) -> Result<DanbooruUpload> {
+        get_json(
+            &self.0,
+            &format!("uploads/{id}.json"),
+            &[("only", UPLOAD_FIELDS.to_string())],
+        )
+        .await
+    }
+
+    /// Polls an upload until processing has finished.
+    ///
+    /// Gives up after 60 checks and returns the upload as last seen, so
+    /// check [`DanbooruUpload::is_completed`] before creating a post.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails or if a response cannot be parsed.
+    pub async fn wait_for_upload(
+        &self,
+        id: u32,
+        poll_interval: Duration,
+    ) -> Result<DanbooruUpload> {
+        let mut upload = self.upload(id).await?;
+
+        for _ in 1..MAX_UPLOAD_POLLS {
+            if upload.is_finished() {
+                break;
+            }
+            tokio::time::sleep(poll_interval).await;
+            upload = self.upload(id).await?;
+        }
+
+        Ok(upload)
+    }
+
+    /// Publishes a processed upload as a new post.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::DuplicatePost`] with the existing post's ID if
+    /// the file has already been posted, [`BooruError::Unauthorized`] if no
+    /// credentials are set or they are invalid, and other errors if the
+    /// request fails or the response cannot be parsed.
+    ///
+    /// [`BooruError::DuplicatePost`]: crate::error::BooruError::DuplicatePost
+    /// [`BooruError::Unauthorized`]: crate::error::BooruError::Unauthorized
+    pub async fn create_post(&self, post: &DanbooruNewPost) -> Result<DanbooruPost> {
+        require_credentials(&self.0)?;
+
+        let response = self
+            .0
+            .send(request(&self.0, Method::POST, "posts.json").form(&post.params()))
+            .await
+            .map_err(duplicate_post)?;
+
+        Ok(response.json().await?)
+    }
+}
+
+/// A post to create from an upload.
+///
+/// Passed to [`DanbooruClient::create_post`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DanbooruNewPost {
+    upload_media_asset_id: u32,
+    tags: Vec<String>,
+    rating: Option<DanbooruRating>,
+    source: Option<String>,
+    parent_id: Option<u32>,
+}
+
+impl DanbooruNewPost {
+    /// Creates a post from an upload media asset with the given tags.
+    ///
+    /// The ID is that of a [`DanbooruUploadMediaAsset`], not of the media
+    /// asset itself.
+    ///
+    /// [`DanbooruUploadMediaAsset`]: crate::model::danbooru::DanbooruUploadMediaAsset
+    pub fn new<I, S>(upload_media_asset_id: u32, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            upload_media_asset_id,
+            tags: tags.into_iter().map(Into::into).collect(),
+            rating: None,
+            source: None,
+            parent_id: None,
+        }
+    }
+
+    /// Sets the rating of the post.
+    ///
+    /// Danbooru requires a rating, either here or as a `rating:` tag.
+    #[must_use]
+    pub fn rating(mut self, rating: DanbooruRating) -> Self {
+        self.rating = Some(rating);
+        self
+    }
+
+    /// Sets the source of the post.
+    #[must_use]
+    pub fn source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Sets the parent of the post.
+    #[must_use]
+    pub fn parent_id(mut self, parent_id: u32) -> Self {
+        self.parent_id = Some(parent_id);
+        self
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![
+            (
+                "upload_media_asset_id",
+                self.upload_media_asset_id.to_string(),
+            ),
+            ("post[tag_string]", self.tags.join(" ")),
+        ];
+
+        if let Some(rating) = self.rating {
+            params.push(("post[rating]", rating_code(rating).to_string()));
+        }
+        if let Some(source) = &self.source {
+            params.push(("post[source]", source.clone()));
+        }
+        if let Some(parent_id) = self.parent_id {
+            params.push(("post[parent_id]", parent_id.to_string()));
+        }
+
+        params
+    }
+}
+
+/// Turns Danbooru's "duplicate" validation error into [`BooruError::DuplicatePost`].
+fn duplicate_post(error: BooruError) -> BooruError {
+    match &error {
+        BooruError::ApiError {
+            status: 422,
+            message,
+            body_snippet,
+        } => duplicate_post_id(message)
+            .or_else(|| duplicate_post_id(body_snippet))
+            .map_or(error, |post_id| BooruError::DuplicatePost { post_id }),
+        _ => error,
+    }
+}
+
+/// Finds the post ID in messages such as `"Md5 duplicate: 12345"`.
+fn duplicate_post_id(text: &str) -> Option<u32> {
+    let start = text.to_ascii_lowercase().find("duplicate")?;
+    let digits: String = text[start..]
+        .chars()
+        .skip_while(|c| !c.is_ascii_digit())
+        .take_while(char::is_ascii_digit)
+        .collect();
+
+    digits.parse().ok()
+}
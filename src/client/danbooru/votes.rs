@@ -0,0 +1,97 @@
+//! Danbooru post votes (`/posts/{id}/votes.json`).
+
+use reqwest::Method;
+
+use super::{DanbooruClient, request, require_credentials};
+use crate::error::Result;
+use crate::model::danbooru::DanbooruPostVote;
+
+impl DanbooruClient {
+    /// Upvotes a post as the authenticated user.
+    ///
+    /// Voting again replaces the user's previous vote on the post.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::Unauthorized`] if no credentials are set or
+    /// they are invalid, and [`BooruError::Forbidden`] if the account may
+    /// not vote. Returns other errors if the request fails or the response
+    /// cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::danbooru::DanbooruClient;
+    /// use booru_rs::client::Client;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// let client = DanbooruClient::builder()
+    ///     .set_credentials("your_api_key", "your_login")
+    ///     .build();
+    ///
+    /// let vote = client.upvote(12345).await?;
+    /// assert!(vote.is_upvote());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`BooruError::Unauthorized`]: crate::error::BooruError::Unauthorized
+    /// [`BooruError::Forbidden`]: crate::error::BooruError::Forbidden
+    pub async fn upvote(&self, post_id: u32) -> Result<DanbooruPostVote> {
+        self.vote(post_id, 1).await
+    }
+
+    /// Downvotes a post as the authenticated user.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`upvote`](Self::upvote).
+    pub async fn downvote(&self, post_id: u32) -> Result<DanbooruPostVote> {
+        self.vote(post_id, -1).await
+    }
+
+    /// Removes the authenticated user's vote from a post.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::Unauthorized`] if no credentials are set or
+    /// they are invalid, and [`BooruError::Forbidden`] if the account lacks
+    /// permission. Returns other errors if the request fails.
+    ///
+    /// [`BooruError::Unauthorized`]: crate::error::BooruError::Unauthorized
+    /// [`BooruError::Forbidden`]: crate::error::BooruError::Forbidden
+    pub async fn unvote(&self, post_id: u32) -> Result<()> {
+        let builder = &self.0;
+        require_credentials(builder)?;
+
+        builder
+            .send(request(
+                builder,
+                Method::DELETE,
+                &format!("posts/{post_id}/votes.json"),
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn vote(&self, post_id: u32, score: i32) -> Result<DanbooruPostVote> {
+        let builder = &self.0;
+        require_credentials(builder)?;
+
+        let vote = builder
+            .send(
+                request(
+                    builder,
+                    Method::POST,
+                    &format!("posts/{post_id}/votes.json"),
+                )
+                .query(&[("score", score.to_string())]),
+            )
+            .await?
+            .json::<DanbooruPostVote>()
+            .await?;
+
+        Ok(vote)
+    }
+}
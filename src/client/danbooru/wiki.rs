@@ -0,0 +1,44 @@
+//! Danbooru wiki pages (`/wiki_pages/{title}.json`).
+
+use super::{DanbooruClient, encode_path_segment, get_json};
+use crate::error::Result;
+use crate::model::danbooru::DanbooruWikiPage;
+
+impl DanbooruClient {
+    /// Retrieves the wiki page with the given title.
+    ///
+    /// Wiki pages share their title with the tag they describe, so this is
+    /// the way to get a tag's description. A title consisting only of digits
+    /// is treated as a wiki page ID by Danbooru.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::ApiError`] with status 404 if no page exists
+    /// with the given title (see [`BooruError::is_not_found`]).
+    /// Returns other errors if the request fails or the response cannot be parsed.
+    ///
+    /// [`BooruError::ApiError`]: crate::error::BooruError::ApiError
+    /// [`BooruError::is_not_found`]: crate::error::BooruError::is_not_found
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::danbooru::DanbooruClient;
+    /// use booru_rs::client::Client;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// let page = DanbooruClient::builder()
+    ///     .build()
+    ///     .wiki_page("cat_ears")
+    ///     .await?;
+    ///
+    /// println!("{}\n\n{}", page.display_title(), page.body);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wiki_page(&self, title: &str) -> Result<DanbooruWikiPage> {
+        let path = format!("wiki_pages/{}.json", encode_path_segment(title));
+
+        get_json(&self.0, &path, &[]).await
+    }
+}
@@ -0,0 +1,45 @@
+//! Gelbooru comments (`page=dapi&s=comment&q=index`).
+
+use super::{GelbooruClient, credentials_hint};
+use crate::client::dapi_comments;
+use crate::error::Result;
+use crate::model::gelbooru::Comment;
+
+impl GelbooruClient {
+    /// Retrieves the comments on a post, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::Unauthorized`] if API credentials are missing or
+    /// invalid, [`BooruError::ApiError`] if Gelbooru answers with an error
+    /// page, and other errors if the request fails or the response cannot be
+    /// parsed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::gelbooru::GelbooruClient;
+    /// use booru_rs::client::Client;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// let comments = GelbooruClient::builder()
+    ///     .set_credentials("your_api_key", "your_user_id")
+    ///     .build()
+    ///     .comments(12345)
+    ///     .await?;
+    ///
+    /// for comment in comments {
+    ///     println!("{}: {}", comment.creator, comment.body);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`BooruError::Unauthorized`]: crate::error::BooruError::Unauthorized
+    /// [`BooruError::ApiError`]: crate::error::BooruError::ApiError
+    pub async fn comments(&self, post_id: u32) -> Result<Vec<Comment>> {
+        dapi_comments(&self.0, post_id)
+            .await
+            .map_err(credentials_hint)
+    }
+}
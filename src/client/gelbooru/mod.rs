@@ -1,10 +1,22 @@
 //! Gelbooru API client implementation.
 
-use super::{Client, ClientBuilder, shared_client};
+use super::{
+    Client, ClientBuilder, PostPage, PostsById, error_for_markup, error_for_status,
+    get_by_ids_chunked, get_by_md5_search, id_or_search, post_not_found, random_by_offset,
+    shared_client,
+};
 use crate::autocomplete::{Autocomplete, TagSuggestion};
 use crate::error::{BooruError, Result};
 use crate::model::gelbooru::*;
+use crate::model::lenient::{LenientPosts, decode_items};
 use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+mod comments;
+mod tags;
+
+pub use tags::*;
 
 /// Client for interacting with the Gelbooru API.
 ///
@@ -63,10 +75,10 @@ impl Client for GelbooruClient {
     ///
     /// Returns [`BooruError::PostNotFound`] if no post exists with the given ID.
     /// Returns [`BooruError::Unauthorized`] if API credentials are missing or invalid.
+    /// Returns [`BooruError::ApiError`] if Gelbooru answers with an error page.
     /// Returns other errors if the request fails or the response cannot be parsed.
     async fn get_by_id(&self, id: u32) -> Result<Self::Post> {
         let builder = &self.0;
-        let url = &builder.url;
 
         let mut query = vec![
             ("page", "dapi".to_string()),
@@ -82,21 +94,9 @@ impl Client for GelbooruClient {
             query.push(("user_id", user.clone()));
         }
 
-        let response = builder
-            .client
-            .get(format!("{url}/index.php"))
-            .query(&query)
-            .send()
-            .await?;
-
-        // Check for authentication errors
-        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
-            return Err(BooruError::Unauthorized(
-                "Gelbooru requires API credentials. Use set_credentials(api_key, user_id)".into(),
-            ));
-        }
-
-        let data = response.json::<GelbooruResponse>().await?;
+        let data: GelbooruResponse = fetch_dapi(&self.0, &query)
+            .await
+            .map_err(post_not_found(id))?;
 
         data.posts
             .into_iter()
@@ -104,15 +104,101 @@ impl Client for GelbooruClient {
             .ok_or(BooruError::PostNotFound(id))
     }
 
+    /// Retrieves many posts by ID with `{id:1 ~ id:2 ~ ...}` searches of up
+    /// to 100 IDs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails or if a response cannot be parsed.
+    async fn get_by_ids(&self, ids: &[u32]) -> Result<PostsById<Self::Post>> {
+        get_by_ids_chunked(&self.0, ids, 100, id_or_search).await
+    }
+
+    /// Retrieves the post whose file has the given MD5 hash.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::InvalidTag`] if `md5` is not a valid MD5 hash,
+    /// and other errors if the request fails or the response cannot be parsed.
+    async fn get_by_md5(&self, md5: &str) -> Result<Option<Self::Post>> {
+        get_by_md5_search(&self.0, md5).await
+    }
+
+    /// Retrieves random posts by fetching single posts at random offsets.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::Unauthorized`] if API credentials are missing or invalid.
+    /// Returns other errors if a request fails or if a response cannot be parsed.
+    async fn random(&self, count: u32) -> Result<Vec<Self::Post>> {
+        random_by_offset(&self.0, count)
+            .await
+            .map_err(credentials_hint)
+    }
+
     /// Retrieves posts matching the configured query.
     ///
     /// # Errors
     ///
     /// Returns [`BooruError::Unauthorized`] if API credentials are missing or invalid.
+    /// Returns [`BooruError::ApiError`] if Gelbooru answers with an error page.
     /// Returns other errors if the request fails or if the response cannot be parsed.
     async fn get(&self) -> Result<Vec<Self::Post>> {
+        Ok(self.get_page().await?.posts)
+    }
+
+    /// Retrieves posts matching the configured query, together with the
+    /// total and offset from the response's `@attributes`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::Unauthorized`] if API credentials are missing or invalid.
+    /// Returns [`BooruError::ApiError`] if Gelbooru answers with an error page.
+    /// Returns other errors if the request fails or if the response cannot be parsed.
+    async fn get_page(&self) -> Result<PostPage<Self::Post>> {
+        let (posts, attributes) = if self.0.lenient {
+            let data: RawGelbooruResponse = fetch_dapi(&self.0, &self.page_query()).await?;
+            (decode_items(data.posts).posts, data.attributes)
+        } else {
+            let data: GelbooruResponse = fetch_dapi(&self.0, &self.page_query()).await?;
+            (data.posts, data.attributes)
+        };
+
+        Ok(PostPage {
+            posts,
+            total: attributes.map(|attributes| attributes.count),
+            offset: attributes.map(|attributes| attributes.offset),
+        })
+    }
+
+    /// Retrieves posts matching the configured query, decoding each post
+    /// separately.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::Unauthorized`] if API credentials are missing or invalid.
+    /// Returns [`BooruError::ApiError`] if Gelbooru answers with an error page.
+    /// Returns other errors if the request fails or if the response is not a post list.
+    async fn get_lenient(&self) -> Result<LenientPosts<Self::Post>> {
+        let data: RawGelbooruResponse = fetch_dapi(&self.0, &self.page_query()).await?;
+
+        Ok(decode_items(data.posts))
+    }
+}
+
+/// Gelbooru response with the posts left undecoded, for lenient mode.
+#[derive(Debug, Deserialize)]
+struct RawGelbooruResponse {
+    #[serde(rename = "@attributes", default)]
+    attributes: Option<GelbooruAttributes>,
+    #[serde(rename = "post", default)]
+    posts: Vec<Value>,
+}
+
+impl GelbooruClient {
+    /// Builds the DAPI query for the configured post search.
+    fn page_query(&self) -> Vec<(&'static str, String)> {
         let builder = &self.0;
-        let url = &builder.url;
         let tag_string = builder.tags.join(" ");
 
         let mut query = vec![
@@ -131,23 +217,36 @@ impl Client for GelbooruClient {
             query.push(("user_id", user.clone()));
         }
 
-        let response = builder
-            .client
-            .get(format!("{url}/index.php"))
-            .query(&query)
-            .send()
-            .await?;
+        query
+    }
+}
 
-        // Check for authentication errors
-        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
-            return Err(BooruError::Unauthorized(
-                "Gelbooru requires API credentials. Use set_credentials(api_key, user_id)".into(),
-            ));
-        }
+/// Sends a query to the DAPI and decodes the JSON response.
+async fn fetch_dapi<R: DeserializeOwned>(
+    builder: &ClientBuilder<GelbooruClient>,
+    query: &[(&str, String)],
+) -> Result<R> {
+    let url = &builder.url;
 
-        let data = response.json::<GelbooruResponse>().await?;
+    let response = builder
+        .send(builder.client.get(format!("{url}/index.php")).query(query))
+        .await
+        .map_err(credentials_hint)?;
 
-        Ok(data.posts)
+    let status = response.status();
+    let text = response.text().await?;
+    error_for_markup(status, &text)?;
+
+    Ok(serde_json::from_str(&text)?)
+}
+
+/// Replaces the server's authentication error with a hint on how to fix it.
+fn credentials_hint(error: BooruError) -> BooruError {
+    match error {
+        BooruError::Unauthorized(_) => BooruError::Unauthorized(
+            "Gelbooru requires API credentials. Use set_credentials(api_key, user_id)".into(),
+        ),
+        error => error,
     }
 }
 
@@ -182,13 +281,11 @@ impl Autocomplete for GelbooruClient {
             .send()
             .await?;
 
-        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
-            return Err(BooruError::Unauthorized(
-                "Gelbooru requires API credentials for some endpoints".into(),
-            ));
-        }
-
-        let items: Vec<GelbooruAutocompleteItem> = response.json().await?;
+        let items: Vec<GelbooruAutocompleteItem> = error_for_status(response)
+            .await
+            .map_err(credentials_hint)?
+            .json()
+            .await?;
 
         Ok(items
             .into_iter()
@@ -0,0 +1,304 @@
+//! Gelbooru tag search (`page=dapi&s=tag&q=index`).
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use super::{GelbooruClient, fetch_dapi};
+use crate::client::ClientBuilder;
+use crate::error::Result;
+use crate::model::gelbooru::{GelbooruTag, GelbooruTagOrder, GelbooruTagType};
+use crate::stream::paginated_query;
+
+/// Number of names looked up per request by [`GelbooruClient::tag_types`].
+const NAMES_PER_REQUEST: usize = 100;
+
+impl GelbooruClient {
+    /// Starts a search of Gelbooru's tags.
+    ///
+    /// The query reuses this client's base URL, credentials, HTTP client,
+    /// rate limiter and circuit breaker.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::gelbooru::{GelbooruClient, GelbooruTagOrder};
+    /// use booru_rs::client::Client;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// let tags = GelbooruClient::builder()
+    ///     .set_credentials("your_api_key", "your_user_id")
+    ///     .build()
+    ///     .tags()
+    ///     .name_pattern("cat_%")
+    ///     .order(GelbooruTagOrder::Count)
+    ///     .limit(20)
+    ///     .get()
+    ///     .await?;
+    ///
+    /// for tag in tags {
+    ///     println!("{} ({:?}): {} posts", tag.name, tag.tag_type(), tag.count);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn tags(&self) -> GelbooruTagQuery {
+        GelbooruTagQuery::new(self.0.clone())
+    }
+
+    /// Looks up the types of many tags at once.
+    ///
+    /// Names are looked up in batches of 100. The result is keyed by the
+    /// names as given, even though Gelbooru answers with HTML-escaped names
+    /// (`&#039;` for `'`). Tags Gelbooru doesn't know, or whose type ID is
+    /// not a known [`GelbooruTagType`], are left out of the result.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::Unauthorized`] if API credentials are missing or
+    /// invalid, and other errors if a request fails or a response cannot be
+    /// parsed.
+    ///
+    /// [`BooruError::Unauthorized`]: crate::error::BooruError::Unauthorized
+    pub async fn tag_types<I, S>(&self, names: I) -> Result<HashMap<String, GelbooruTagType>>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut names: Vec<String> = names.into_iter().map(Into::into).collect();
+        names.sort_unstable();
+        names.dedup();
+
+        let mut types = HashMap::with_capacity(names.len());
+        for chunk in names.chunks(NAMES_PER_REQUEST) {
+            let tags = self
+                .tags()
+                .names(chunk.iter().cloned())
+                .limit(chunk.len() as u32)
+                .get()
+                .await?;
+
+            let requested: HashMap<Cow<str>, &String> = chunk
+                .iter()
+                .map(|name| (unescape_html(name), name))
+                .collect();
+
+            types.extend(tags.into_iter().filter_map(|tag| {
+                let tag_type = tag.tag_type()?;
+                let name = requested.get(&unescape_html(&tag.name))?;
+                Some(((*name).clone(), tag_type))
+            }));
+        }
+
+        Ok(types)
+    }
+}
+
+/// Replaces the HTML character references Gelbooru uses in tag names, such
+/// as `&#039;` and `&amp;`, with the characters they stand for.
+///
+/// Unknown or malformed references are kept as written.
+fn unescape_html(text: &str) -> Cow<'_, str> {
+    if !text.contains('&') {
+        return Cow::Borrowed(text);
+    }
+
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        unescaped.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let character = rest.find(';').and_then(|end| {
+            let character = match &rest[1..end] {
+                "amp" => '&',
+                "lt" => '<',
+                "gt" => '>',
+                "quot" => '"',
+                "apos" => '\'',
+                reference => {
+                    let code = reference.strip_prefix('#')?;
+                    let code = match code.strip_prefix(['x', 'X']) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => code.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((character, end))
+        });
+
+        match character {
+            Some((character, end)) => {
+                unescaped.push(character);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                unescaped.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    unescaped.push_str(rest);
+
+    Cow::Owned(unescaped)
+}
+
+/// Gelbooru tag response; the tag list is left out when nothing matches.
+#[derive(Debug, Deserialize)]
+struct GelbooruTagResponse {
+    #[serde(rename = "tag", default)]
+    tags: Vec<GelbooruTag>,
+}
+
+/// A search of Gelbooru's tags.
+///
+/// Created by [`GelbooruClient::tags`]. Results can be fetched one page at
+/// a time with [`get`](Self::get), or streamed with
+/// [`into_page_stream`](Self::into_page_stream) and
+/// [`into_tag_stream`](Self::into_tag_stream).
+#[derive(Debug, Clone)]
+pub struct GelbooruTagQuery {
+    builder: ClientBuilder<GelbooruClient>,
+    id: Option<u32>,
+    name: Option<String>,
+    names: Vec<String>,
+    name_pattern: Option<String>,
+    order: Option<GelbooruTagOrder>,
+    ascending: bool,
+    limit: u32,
+    page: u32,
+}
+
+impl GelbooruTagQuery {
+    fn new(builder: ClientBuilder<GelbooruClient>) -> Self {
+        Self {
+            builder,
+            id: None,
+            name: None,
+            names: Vec::new(),
+            name_pattern: None,
+            order: None,
+            ascending: false,
+            limit: 100,
+            page: 0,
+        }
+    }
+
+    /// Only returns the tag with this ID.
+    #[must_use]
+    pub fn id(mut self, id: u32) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Only returns the tag with exactly this name.
+    #[must_use]
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Only returns the tags with exactly these names.
+    #[must_use]
+    pub fn names<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.names.extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// Only returns tags whose name matches a pattern.
+    ///
+    /// Gelbooru uses SQL `LIKE` patterns: `%` matches any sequence of
+    /// characters and `_` any single character, e.g. `"cat%"`.
+    #[must_use]
+    pub fn name_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.name_pattern = Some(pattern.into());
+        self
+    }
+
+    /// Sets the sort order of the results, descending by default.
+    #[must_use]
+    pub fn order(mut self, order: GelbooruTagOrder) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    /// Sorts the results in ascending order instead.
+    ///
+    /// Only applies together with [`order`](Self::order).
+    #[must_use]
+    pub fn ascending(mut self) -> Self {
+        self.ascending = true;
+        self
+    }
+
+    /// Fetches the configured page of tags.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::Unauthorized`] if API credentials are missing or
+    /// invalid, [`BooruError::ApiError`] if Gelbooru answers with an error
+    /// page, and other errors if the request fails or the response cannot be
+    /// parsed.
+    ///
+    /// [`BooruError::Unauthorized`]: crate::error::BooruError::Unauthorized
+    /// [`BooruError::ApiError`]: crate::error::BooruError::ApiError
+    pub async fn get(&self) -> Result<Vec<GelbooruTag>> {
+        let response: GelbooruTagResponse = fetch_dapi(&self.builder, &self.params()).await?;
+
+        Ok(response.tags)
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        let builder = &self.builder;
+
+        let mut params = vec![
+            ("page", "dapi".to_string()),
+            ("s", "tag".to_string()),
+            ("q", "index".to_string()),
+            ("pid", self.page.to_string()),
+            ("limit", self.limit.to_string()),
+            ("json", "1".to_string()),
+        ];
+
+        if let Some(id) = self.id {
+            params.push(("id", id.to_string()));
+        }
+        if let Some(name) = &self.name {
+            params.push(("name", name.clone()));
+        }
+        if !self.names.is_empty() {
+            params.push(("names", self.names.join(" ")));
+        }
+        if let Some(pattern) = &self.name_pattern {
+            params.push(("name_pattern", pattern.clone()));
+        }
+        if let Some(order) = self.order {
+            params.push(("orderby", order.to_string()));
+            let direction = if self.ascending { "ASC" } else { "DESC" };
+            params.push(("order", direction.to_string()));
+        }
+
+        // Add API credentials if provided
+        if let (Some(key), Some(user)) = (&builder.key, &builder.user) {
+            params.push(("api_key", key.clone()));
+            params.push(("user_id", user.clone()));
+        }
+
+        params
+    }
+}
+
+paginated_query! {
+    GelbooruTagQuery => GelbooruTag,
+    noun: "tags",
+    stream: into_tag_stream,
+    limit: "Default is 100.",
+    page: "Page numbering starts at 0, which is the default.",
+}
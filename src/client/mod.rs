@@ -58,10 +58,30 @@
 //! # }
 //! ```
 
+#[cfg(any(
+    feature = "danbooru",
+    feature = "gelbooru",
+    feature = "rule34",
+    feature = "safebooru"
+))]
+use std::collections::HashSet;
 use std::sync::LazyLock;
+#[cfg(any(
+    feature = "danbooru",
+    feature = "gelbooru",
+    feature = "rule34",
+    feature = "safebooru"
+))]
+use std::sync::{Arc, Mutex, PoisonError};
 use std::time::Duration;
 
+use crate::circuit::CircuitBreaker;
 use crate::error::{BooruError, Result};
+use crate::model::Post;
+use crate::model::lenient::LenientPosts;
+use crate::ratelimit::RateLimiter;
+#[cfg(any(feature = "gelbooru", feature = "rule34", feature = "safebooru"))]
+use crate::retry::random_unit;
 
 #[cfg(feature = "danbooru")]
 pub mod danbooru;
@@ -124,6 +144,18 @@ pub struct ClientBuilder<T: Client> {
     pub(crate) limit: u32,
     pub(crate) url: String,
     pub(crate) page: u32,
+    pub(crate) rate_limiter: Option<RateLimiter>,
+    pub(crate) circuit_breaker: Option<CircuitBreaker>,
+    pub(crate) lenient: bool,
+    /// IDs of the posts already returned by [`Client::random`], shared
+    /// between clones.
+    #[cfg(any(
+        feature = "danbooru",
+        feature = "gelbooru",
+        feature = "rule34",
+        feature = "safebooru"
+    ))]
+    pub(crate) random_seen: Arc<Mutex<HashSet<u32>>>,
     _marker: std::marker::PhantomData<T>,
 }
 
@@ -137,6 +169,16 @@ impl<T: Client> Clone for ClientBuilder<T> {
             limit: self.limit,
             url: self.url.clone(),
             page: self.page,
+            rate_limiter: self.rate_limiter.clone(),
+            circuit_breaker: self.circuit_breaker.clone(),
+            lenient: self.lenient,
+            #[cfg(any(
+                feature = "danbooru",
+                feature = "gelbooru",
+                feature = "rule34",
+                feature = "safebooru"
+            ))]
+            random_seen: Arc::clone(&self.random_seen),
             _marker: std::marker::PhantomData,
         }
     }
@@ -160,7 +202,7 @@ impl<T: Client> Clone for ClientBuilder<T> {
 /// - `MAX_TAGS`: Optional limit on the number of tags per query
 pub trait Client: From<ClientBuilder<Self>> + Sized + Send + Sync {
     /// The post type returned by this client.
-    type Post: Send;
+    type Post: Post + Send;
 
     /// The rating type for this booru site.
     type Rating: Into<String> + Send;
@@ -187,12 +229,260 @@ pub trait Client: From<ClientBuilder<Self>> + Sized + Send + Sync {
     /// Returns an error if the request fails or if the post is not found.
     fn get_by_id(&self, id: u32) -> impl std::future::Future<Output = Result<Self::Post>> + Send;
 
+    /// Retrieves many posts by ID, using as few requests as the site allows.
+    ///
+    /// IDs are looked up in batches with the site's ID search instead of one
+    /// request per post. The configured tags, rating, sort order, limit and
+    /// page are ignored.
+    ///
+    /// Posts are returned in the order of `ids`, once per ID. IDs without a
+    /// matching post (deleted, hidden or nonexistent) are listed in
+    /// [`PostsById::missing`] instead of failing the call.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails or if a response cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::prelude::*;
+    ///
+    /// # async fn example() -> Result<()> {
+    /// let result = DanbooruClient::builder()
+    ///     .build()
+    ///     .get_by_ids(&[1, 2, 3])
+    ///     .await?;
+    ///
+    /// println!("Found {} posts", result.posts.len());
+    /// for id in &result.missing {
+    ///     eprintln!("post #{id} is gone");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn get_by_ids(
+        &self,
+        ids: &[u32],
+    ) -> impl std::future::Future<Output = Result<PostsById<Self::Post>>> + Send;
+
+    /// Retrieves the post whose file has the given MD5 hash.
+    ///
+    /// Returns `None` if the site has no post with this file. The configured
+    /// tags, rating and sort order are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::InvalidTag`] if `md5` is not 32 hexadecimal
+    /// characters, and other errors if the request fails or the response
+    /// cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::prelude::*;
+    ///
+    /// # async fn example() -> Result<()> {
+    /// let post = SafebooruClient::builder()
+    ///     .build()
+    ///     .get_by_md5("d41d8cd98f00b204e9800998ecf8427e")
+    ///     .await?;
+    ///
+    /// if let Some(post) = post {
+    ///     println!("Found as post #{}", post.id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn get_by_md5(
+        &self,
+        md5: &str,
+    ) -> impl std::future::Future<Output = Result<Option<Self::Post>>> + Send;
+
+    /// Retrieves up to `count` random posts matching the configured tags.
+    ///
+    /// Posts returned by earlier calls on the same client, or on clients
+    /// built from clones of its builder, are not returned again, so fewer
+    /// than `count` posts are returned once few unseen posts remain. Up to
+    /// 10,000 posts are remembered; after that the history starts over.
+    ///
+    /// The configured limit and page are ignored, and sort tags (such as
+    /// those added by [`ClientBuilder::sort`] or
+    /// [`ClientBuilder::random`]) are left out of the search.
+    ///
+    /// Danbooru picks the posts with its random post endpoint. Gelbooru-based
+    /// sites have none, so posts are fetched one at a time from random
+    /// offsets within the search results (at most the first 20,000, as
+    /// deeper pages are refused).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails or if a response cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::prelude::*;
+    ///
+    /// # async fn example() -> Result<()> {
+    /// let client = SafebooruClient::builder().tag("landscape")?.build();
+    ///
+    /// // Two batches of five, with no post appearing twice
+    /// let first = client.random(5).await?;
+    /// let second = client.random(5).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn random(
+        &self,
+        count: u32,
+    ) -> impl std::future::Future<Output = Result<Vec<Self::Post>>> + Send;
+
     /// Retrieves posts matching the configured query.
     ///
     /// # Errors
     ///
     /// Returns an error if the request fails or if the response cannot be parsed.
     fn get(&self) -> impl std::future::Future<Output = Result<Vec<Self::Post>>> + Send;
+
+    /// Retrieves posts matching the configured query, together with the
+    /// total number of matching posts and the offset of the page.
+    ///
+    /// Gelbooru reports both in the same response. Safebooru and Rule34 only
+    /// report the total in their XML API, whose posts lack fields of the
+    /// JSON ones, so on those sites this makes **two requests**: the JSON
+    /// page and the XML post count. Both count against the rate limiter;
+    /// use [`get`](Self::get) when the total isn't needed. The default
+    /// implementation falls back to [`get`](Self::get) and reports neither.
+    ///
+    /// Post streams use this until a total is reported (on the first page
+    /// for the built-in clients), to stop after the last page instead of
+    /// fetching a trailing empty one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or if the response cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::prelude::*;
+    ///
+    /// # async fn example() -> Result<()> {
+    /// let page = SafebooruClient::builder()
+    ///     .tag("landscape")?
+    ///     .limit(20)
+    ///     .page(3)
+    ///     .build()
+    ///     .get_page()
+    ///     .await?;
+    ///
+    /// if let (Some(total), Some(offset)) = (page.total, page.offset) {
+    ///     println!("{}-{} of {total}", offset + 1, offset + page.posts.len() as u64);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn get_page(&self) -> impl std::future::Future<Output = Result<PostPage<Self::Post>>> + Send {
+        async { self.get().await.map(PostPage::from) }
+    }
+
+    /// Retrieves posts matching the configured query, decoding each post
+    /// separately.
+    ///
+    /// Posts that fail to decode are reported in [`LenientPosts::errors`]
+    /// instead of failing the whole page. The default implementation falls
+    /// back to [`get`](Self::get) and never reports per-item errors.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or if the response as a whole
+    /// cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::prelude::*;
+    ///
+    /// # async fn example() -> Result<()> {
+    /// let page = DanbooruClient::builder()
+    ///     .tag("cat_ears")?
+    ///     .build()
+    ///     .get_lenient()
+    ///     .await?;
+    ///
+    /// for error in &page.errors {
+    ///     eprintln!("skipped {error}");
+    /// }
+    /// println!("Decoded {} posts", page.posts.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn get_lenient(
+        &self,
+    ) -> impl std::future::Future<Output = Result<LenientPosts<Self::Post>>> + Send {
+        async { self.get().await.map(LenientPosts::from) }
+    }
+}
+
+/// Posts retrieved by [`Client::get_by_ids`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PostsById<P> {
+    /// Posts that were found, in the order their IDs were requested.
+    pub posts: Vec<P>,
+    /// Requested IDs for which no post was returned.
+    pub missing: Vec<u32>,
+}
+
+impl<P> PostsById<P> {
+    /// Returns `true` if a post was found for every requested ID.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// A page of search results retrieved by [`Client::get_page`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PostPage<P> {
+    /// Posts on this page
+    pub posts: Vec<P>,
+    /// Total number of posts matching the search, if the site reports it
+    pub total: Option<u64>,
+    /// Number of matching posts before this page, if the site reports it
+    pub offset: Option<u64>,
+}
+
+impl<P> PostPage<P> {
+    /// Returns `true` if no more posts follow this page.
+    ///
+    /// Without a total and offset, only an empty page is known to be the
+    /// last one.
+    #[must_use]
+    pub fn is_last(&self) -> bool {
+        match (self.total, self.offset) {
+            (Some(total), Some(offset)) => offset + self.posts.len() as u64 >= total,
+            _ => self.posts.is_empty(),
+        }
+    }
+
+    /// Returns the number of matching posts after this page, if known.
+    #[must_use]
+    pub fn remaining(&self) -> Option<u64> {
+        let seen = self.offset? + self.posts.len() as u64;
+        Some(self.total?.saturating_sub(seen))
+    }
+}
+
+impl<P> From<Vec<P>> for PostPage<P> {
+    /// Wraps posts from a site that reports no total.
+    fn from(posts: Vec<P>) -> Self {
+        Self {
+            posts,
+            total: None,
+            offset: None,
+        }
+    }
 }
 
 impl<T: Client> ClientBuilder<T> {
@@ -209,6 +499,16 @@ impl<T: Client> ClientBuilder<T> {
             limit: 100,
             url: T::URL.to_string(),
             page: 0,
+            rate_limiter: None,
+            circuit_breaker: None,
+            lenient: false,
+            #[cfg(any(
+                feature = "danbooru",
+                feature = "gelbooru",
+                feature = "rule34",
+                feature = "safebooru"
+            ))]
+            random_seen: Arc::default(),
             _marker: std::marker::PhantomData,
         }
     }
@@ -226,6 +526,16 @@ impl<T: Client> ClientBuilder<T> {
             limit: 100,
             url: T::URL.to_string(),
             page: 0,
+            rate_limiter: None,
+            circuit_breaker: None,
+            lenient: false,
+            #[cfg(any(
+                feature = "danbooru",
+                feature = "gelbooru",
+                feature = "rule34",
+                feature = "safebooru"
+            ))]
+            random_seen: Arc::default(),
             _marker: std::marker::PhantomData,
         }
     }
@@ -249,6 +559,63 @@ impl<T: Client> ClientBuilder<T> {
         self
     }
 
+    /// Sets a rate limiter that every request made by the client goes through.
+    ///
+    /// The limiter is shared, so the same instance can be passed to several
+    /// builders to enforce a combined limit. Adaptive limiters (see
+    /// [`RateLimiter::adaptive`]) are fed each response so they can react to
+    /// HTTP 429/503 and rate limit headers.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// let limiter = RateLimiter::adaptive(2, Duration::from_secs(1));
+    /// let client = SafebooruClient::builder()
+    ///     .rate_limiter(limiter)
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Sets a circuit breaker that every request made by the client goes through.
+    ///
+    /// While the breaker's circuit for the API host is open, requests fail
+    /// immediately with [`BooruError::CircuitOpen`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::circuit::CircuitBreaker;
+    /// use booru_rs::prelude::*;
+    ///
+    /// let breaker = CircuitBreaker::new();
+    /// let client = GelbooruClient::builder()
+    ///     .circuit_breaker(breaker)
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.circuit_breaker = Some(breaker);
+        self
+    }
+
+    /// Enables lenient decoding for [`Client::get`].
+    ///
+    /// In lenient mode, posts that fail to decode are skipped instead of
+    /// failing the whole page. Use [`Client::get_lenient`] to also find out
+    /// which posts were skipped and why.
+    #[must_use]
+    pub fn lenient(mut self) -> Self {
+        self.lenient = true;
+        self
+    }
+
     /// Adds a tag to the search query.
     ///
     /// # Errors
@@ -318,6 +685,9 @@ impl<T: Client> ClientBuilder<T> {
     }
 
     /// Enables random ordering of results.
+    ///
+    /// This only adds a random sort tag to the search. To pick random posts
+    /// without repeats, use [`Client::random`].
     #[must_use]
     pub fn random(mut self) -> Self {
         self.tags.push(format!("{}random", T::SORT));
@@ -432,6 +802,373 @@ impl<T: Client> ClientBuilder<T> {
     pub fn build(self) -> T {
         T::from(self)
     }
+
+    /// Records a post returned by [`Client::random`], returning `false` if it
+    /// was returned before.
+    ///
+    /// The history is forgotten once it holds [`MAX_RANDOM_SEEN`] posts.
+    #[cfg(any(
+        feature = "danbooru",
+        feature = "gelbooru",
+        feature = "rule34",
+        feature = "safebooru"
+    ))]
+    pub(crate) fn remember_random(&self, id: u32) -> bool {
+        let mut seen = self
+            .random_seen
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if seen.len() >= MAX_RANDOM_SEEN && !seen.contains(&id) {
+            seen.clear();
+        }
+        seen.insert(id)
+    }
+
+    /// Returns the configured tags without sort tags, for queries whose
+    /// order is picked by [`Client::random`].
+    #[cfg(any(
+        feature = "danbooru",
+        feature = "gelbooru",
+        feature = "rule34",
+        feature = "safebooru"
+    ))]
+    pub(crate) fn tags_without_sort(&self) -> Vec<String> {
+        self.tags
+            .iter()
+            .filter(|tag| !tag.starts_with(T::SORT))
+            .cloned()
+            .collect()
+    }
+
+    /// Sends a request, applying the builder's rate limiter and circuit
+    /// breaker if configured.
+    #[cfg(any(
+        feature = "danbooru",
+        feature = "gelbooru",
+        feature = "rule34",
+        feature = "safebooru"
+    ))]
+    pub(crate) async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        let request = request.build()?;
+        let host = crate::circuit::host_key(request.url());
+
+        if let Some(breaker) = &self.circuit_breaker {
+            breaker.check(&host)?;
+        }
+
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.acquire().await;
+        }
+
+        let outcome = self.client.execute(request).await;
+        if let Some(breaker) = &self.circuit_breaker {
+            breaker.record_outcome(&host, &outcome);
+        }
+        let response = outcome?;
+
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.observe(response.status(), response.headers()).await;
+        }
+
+        error_for_status(response).await
+    }
+}
+
+/// Converts a non-success response into the matching [`BooruError`].
+///
+/// The body is read so that API error messages can be included in the error.
+#[cfg(any(
+    feature = "danbooru",
+    feature = "gelbooru",
+    feature = "rule34",
+    feature = "safebooru"
+))]
+pub(crate) async fn error_for_status(response: reqwest::Response) -> Result<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let headers = response.headers().clone();
+    let body = response.text().await.unwrap_or_default();
+    Err(BooruError::from_response(status, &headers, &body))
+}
+
+/// Returns an error for a successful response whose body is an XML or HTML
+/// error page instead of the expected JSON.
+///
+/// Gelbooru-based sites sometimes answer API errors this way with HTTP 200.
+#[cfg(any(feature = "gelbooru", feature = "rule34"))]
+pub(crate) fn error_for_markup(status: reqwest::StatusCode, body: &str) -> Result<()> {
+    if body.trim_start().starts_with('<') {
+        return Err(BooruError::from_response(
+            status,
+            &reqwest::header::HeaderMap::new(),
+            body,
+        ));
+    }
+    Ok(())
+}
+
+/// Looks up posts by ID in chunks, with one post search per chunk.
+///
+/// `id_tag` turns a chunk of IDs into the search tag matching exactly those
+/// posts. Duplicate IDs are looked up once.
+#[cfg(any(
+    feature = "danbooru",
+    feature = "gelbooru",
+    feature = "rule34",
+    feature = "safebooru"
+))]
+pub(crate) async fn get_by_ids_chunked<T: Client>(
+    builder: &ClientBuilder<T>,
+    ids: &[u32],
+    chunk_size: usize,
+    id_tag: fn(&[u32]) -> String,
+) -> Result<PostsById<T::Post>> {
+    let mut seen = HashSet::new();
+    let requested: Vec<u32> = ids.iter().copied().filter(|id| seen.insert(*id)).collect();
+
+    let mut found = std::collections::HashMap::with_capacity(requested.len());
+    for chunk in requested.chunks(chunk_size) {
+        let mut query = builder.clone();
+        query.tags = vec![id_tag(chunk)];
+        query.limit = chunk.len() as u32;
+        query.page = 0;
+
+        for post in query.build().get().await? {
+            found.insert(post.id(), post);
+        }
+    }
+
+    let mut posts = Vec::with_capacity(found.len());
+    let mut missing = Vec::new();
+    for id in requested {
+        match found.remove(&id) {
+            Some(post) => posts.push(post),
+            None => missing.push(id),
+        }
+    }
+
+    Ok(PostsById { posts, missing })
+}
+
+/// Searches for the post whose file has the given MD5 hash with an `md5:`
+/// search.
+#[cfg(any(
+    feature = "danbooru",
+    feature = "gelbooru",
+    feature = "rule34",
+    feature = "safebooru"
+))]
+pub(crate) async fn get_by_md5_search<T: Client>(
+    builder: &ClientBuilder<T>,
+    md5: &str,
+) -> Result<Option<T::Post>> {
+    let md5 = normalize_md5(md5)?;
+
+    let mut query = builder.clone();
+    query.tags = vec![format!("md5:{md5}")];
+    query.limit = 1;
+    query.page = 0;
+
+    Ok(query.build().get().await?.into_iter().next())
+}
+
+/// Checks that `md5` is a hex-encoded MD5 hash and returns it in lowercase.
+#[cfg(any(
+    feature = "danbooru",
+    feature = "gelbooru",
+    feature = "rule34",
+    feature = "safebooru"
+))]
+pub(crate) fn normalize_md5(md5: &str) -> Result<String> {
+    let md5 = md5.trim();
+    if md5.len() != 32 || !md5.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(BooruError::InvalidTag {
+            tag: format!("md5:{md5}"),
+            reason: "an MD5 hash must be 32 hexadecimal characters".into(),
+        });
+    }
+
+    Ok(md5.to_ascii_lowercase())
+}
+
+/// Number of requests [`Client::random`] may make per requested post before
+/// giving up on finding unseen posts.
+#[cfg(any(
+    feature = "danbooru",
+    feature = "gelbooru",
+    feature = "rule34",
+    feature = "safebooru"
+))]
+pub(crate) const RANDOM_ATTEMPTS_PER_POST: u32 = 4;
+
+/// Number of posts [`Client::random`] remembers before starting over.
+#[cfg(any(
+    feature = "danbooru",
+    feature = "gelbooru",
+    feature = "rule34",
+    feature = "safebooru"
+))]
+const MAX_RANDOM_SEEN: usize = 10_000;
+
+/// Deepest result offset Gelbooru-based sites will page to.
+#[cfg(any(feature = "gelbooru", feature = "rule34", feature = "safebooru"))]
+const MAX_DAPI_OFFSET: u64 = 20_000;
+
+/// Picks random posts by fetching single posts at random offsets of the
+/// configured search, for Gelbooru-based sites.
+#[cfg(any(feature = "gelbooru", feature = "rule34", feature = "safebooru"))]
+pub(crate) async fn random_by_offset<T: Client>(
+    builder: &ClientBuilder<T>,
+    count: u32,
+) -> Result<Vec<T::Post>> {
+    let mut search = builder.clone();
+    search.tags = builder.tags_without_sort();
+
+    let total = dapi_post_count(&search).await?.min(MAX_DAPI_OFFSET);
+    let max_requests = count.saturating_mul(RANDOM_ATTEMPTS_PER_POST);
+
+    let mut tried = HashSet::new();
+    let mut requests = 0;
+    let mut posts = Vec::new();
+    while posts.len() < count as usize && requests < max_requests && (tried.len() as u64) < total {
+        let offset = (random_unit() * total as f64) as u64;
+        if !tried.insert(offset) {
+            continue;
+        }
+        requests += 1;
+
+        let mut query = search.clone();
+        query.limit = 1;
+        query.page = offset as u32;
+
+        if let Some(post) = query.build().get().await?.into_iter().next()
+            && builder.remember_random(post.id())
+        {
+            posts.push(post);
+        }
+    }
+
+    Ok(posts)
+}
+
+/// Returns the number of posts matching the configured search on a
+/// Gelbooru-based site.
+///
+/// Not every site includes the total in its JSON responses, so this asks
+/// for the XML post list, whose root element carries it:
+/// `<posts count="1234" offset="0">`.
+#[cfg(any(feature = "gelbooru", feature = "rule34", feature = "safebooru"))]
+async fn dapi_post_count<T: Client>(builder: &ClientBuilder<T>) -> Result<u64> {
+    let url = &builder.url;
+    let mut query = vec![
+        ("page", "dapi".to_string()),
+        ("s", "post".to_string()),
+        ("q", "index".to_string()),
+        ("limit", "1".to_string()),
+        ("tags", builder.tags.join(" ")),
+    ];
+    if let (Some(key), Some(user)) = (&builder.key, &builder.user) {
+        query.push(("api_key", key.clone()));
+        query.push(("user_id", user.clone()));
+    }
+
+    let response = builder
+        .send(builder.client.get(format!("{url}/index.php")).query(&query))
+        .await?;
+    let status = response.status();
+    let text = response.text().await?;
+
+    xml_post_count(&text)
+        .ok_or_else(|| BooruError::from_response(status, &reqwest::header::HeaderMap::new(), &text))
+}
+
+/// Reads the `count` attribute of the `<posts>` element of a DAPI XML
+/// response.
+#[cfg(any(feature = "gelbooru", feature = "rule34", feature = "safebooru"))]
+fn xml_post_count(xml: &str) -> Option<u64> {
+    crate::model::xml::parse_list(xml, "posts", "post")?
+        .ok()?
+        .number("count")
+}
+
+/// Retrieves a page of posts with [`Client::get`] and its total with
+/// [`dapi_post_count`], for Gelbooru-based sites whose JSON API doesn't
+/// report the total.
+#[cfg(any(feature = "rule34", feature = "safebooru"))]
+pub(crate) async fn dapi_post_page<T: Client>(
+    client: &T,
+    builder: &ClientBuilder<T>,
+) -> Result<PostPage<T::Post>> {
+    let posts = client.get().await?;
+    let total = dapi_post_count(builder).await?;
+
+    Ok(PostPage {
+        posts,
+        total: Some(total),
+        offset: Some(u64::from(builder.page) * u64::from(builder.limit)),
+    })
+}
+
+/// Retrieves the comments on a post from the XML comment API of a
+/// Gelbooru-based site.
+#[cfg(any(feature = "gelbooru", feature = "rule34"))]
+pub(crate) async fn dapi_comments<T: Client>(
+    builder: &ClientBuilder<T>,
+    post_id: u32,
+) -> Result<Vec<crate::model::comment::Comment>> {
+    let url = &builder.url;
+    let mut query = vec![
+        ("page", "dapi".to_string()),
+        ("s", "comment".to_string()),
+        ("q", "index".to_string()),
+        ("post_id", post_id.to_string()),
+    ];
+    if let (Some(key), Some(user)) = (&builder.key, &builder.user) {
+        query.push(("api_key", key.clone()));
+        query.push(("user_id", user.clone()));
+    }
+
+    let response = builder
+        .send(builder.client.get(format!("{url}/index.php")).query(&query))
+        .await?;
+    let status = response.status();
+    let text = response.text().await?;
+
+    match crate::model::comment::parse_comment_list(&text) {
+        Some(comments) => Ok(comments?),
+        None => Err(BooruError::from_response(
+            status,
+            &reqwest::header::HeaderMap::new(),
+            &text,
+        )),
+    }
+}
+
+/// Returns an OR search matching any of the IDs, such as `{id:1 ~ id:2}`.
+///
+/// Used by Gelbooru-based sites, whose `id:` metatag takes a single ID.
+#[cfg(any(feature = "gelbooru", feature = "rule34", feature = "safebooru"))]
+pub(crate) fn id_or_search(ids: &[u32]) -> String {
+    let terms: Vec<String> = ids.iter().map(|id| format!("id:{id}")).collect();
+
+    format!("{{{}}}", terms.join(" ~ "))
+}
+
+/// Maps a "not found" API error to [`BooruError::PostNotFound`].
+#[cfg(any(
+    feature = "danbooru",
+    feature = "gelbooru",
+    feature = "rule34",
+    feature = "safebooru"
+))]
+pub(crate) fn post_not_found(id: u32) -> impl FnOnce(BooruError) -> BooruError {
+    move |error| match error {
+        BooruError::ApiError { status: 404, .. } => BooruError::PostNotFound(id),
+        error => error,
+    }
 }
 
 impl<T: Client> Default for ClientBuilder<T> {
@@ -1,10 +1,16 @@
 //! Rule34 API client implementation.
 
-use super::{Client, ClientBuilder, shared_client};
+use super::{
+    Client, ClientBuilder, PostPage, PostsById, dapi_comments, dapi_post_page, error_for_markup,
+    error_for_status, get_by_ids_chunked, get_by_md5_search, id_or_search, post_not_found,
+    random_by_offset, shared_client,
+};
 use crate::autocomplete::{Autocomplete, TagSuggestion};
 use crate::error::{BooruError, Result};
+use crate::model::lenient::{LenientPosts, decode_items};
 use crate::model::rule34::*;
 use serde::Deserialize;
+use serde_json::Value;
 
 /// Client for interacting with the Rule34 API.
 ///
@@ -68,10 +74,10 @@ impl Client for Rule34Client {
     ///
     /// Returns [`BooruError::PostNotFound`] if no post exists with the given ID.
     /// Returns [`BooruError::Unauthorized`] if API credentials are missing or invalid.
+    /// Returns [`BooruError::ApiError`] if Rule34 answers with an error page.
     /// Returns other errors if the request fails or the response cannot be parsed.
     async fn get_by_id(&self, id: u32) -> Result<Self::Post> {
         let builder = &self.0;
-        let url = &builder.url;
 
         let mut query = vec![
             ("page", "dapi".to_string()),
@@ -87,42 +93,112 @@ impl Client for Rule34Client {
             query.push(("user_id", user.clone()));
         }
 
-        let response = builder
-            .client
-            .get(format!("{url}/index.php"))
-            .query(&query)
-            .send()
-            .await?;
-
-        // Check for authentication errors (some APIs may return 401)
-        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
-            return Err(BooruError::Unauthorized(
-                "Rule34 requires API credentials. Use set_credentials(api_key, user_id)".into(),
-            ));
-        }
+        let text = self.fetch_text(&query).await.map_err(post_not_found(id))?;
 
-        // Rule34 API quirk: returns HTTP 200 OK with error message in body instead of 401
-        // Example: "Missing authentication. Go to api.rule34.xxx for more information"
-        let text = response.text().await?;
-        if text.contains("Missing authentication") {
-            return Err(BooruError::Unauthorized(
-                "Rule34 requires API credentials. Use set_credentials(api_key, user_id)".into(),
-            ));
+        // Handle empty response (no results)
+        if text.is_empty() || text == "[]" {
+            return Err(BooruError::PostNotFound(id));
         }
 
         let posts: Vec<Rule34Post> = serde_json::from_str(&text)?;
         posts.into_iter().next().ok_or(BooruError::PostNotFound(id))
     }
 
+    /// Retrieves many posts by ID with `{id:1 ~ id:2 ~ ...}` searches of up
+    /// to 100 IDs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails or if a response cannot be parsed.
+    async fn get_by_ids(&self, ids: &[u32]) -> Result<PostsById<Self::Post>> {
+        get_by_ids_chunked(&self.0, ids, 100, id_or_search).await
+    }
+
+    /// Retrieves the post whose file has the given MD5 hash.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::InvalidTag`] if `md5` is not a valid MD5 hash,
+    /// and other errors if the request fails or the response cannot be parsed.
+    async fn get_by_md5(&self, md5: &str) -> Result<Option<Self::Post>> {
+        get_by_md5_search(&self.0, md5).await
+    }
+
+    /// Retrieves random posts by fetching single posts at random offsets.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::Unauthorized`] if API credentials are missing or invalid.
+    /// Returns other errors if a request fails or if a response cannot be parsed.
+    async fn random(&self, count: u32) -> Result<Vec<Self::Post>> {
+        random_by_offset(&self.0, count)
+            .await
+            .map_err(credentials_hint)
+    }
+
     /// Retrieves posts matching the configured query.
     ///
     /// # Errors
     ///
     /// Returns [`BooruError::Unauthorized`] if API credentials are missing or invalid.
+    /// Returns [`BooruError::ApiError`] if Rule34 answers with an error page.
     /// Returns other errors if the request fails or if the response cannot be parsed.
     async fn get(&self) -> Result<Vec<Self::Post>> {
+        if self.0.lenient {
+            return Ok(self.get_lenient().await?.posts);
+        }
+
+        let text = self.fetch_text(&self.page_query()).await?;
+
+        // Handle empty response (no results)
+        if text.is_empty() || text == "[]" {
+            return Ok(Vec::new());
+        }
+
+        let posts: Vec<Rule34Post> = serde_json::from_str(&text)?;
+        Ok(posts)
+    }
+
+    /// Retrieves posts matching the configured query, together with the
+    /// total and offset.
+    ///
+    /// Rule34's JSON API doesn't report the total, so it is fetched with a
+    /// second request for the XML post list.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::Unauthorized`] if API credentials are missing or invalid.
+    /// Returns [`BooruError::ApiError`] if Rule34 answers with an error page.
+    /// Returns other errors if a request fails or if a response cannot be parsed.
+    async fn get_page(&self) -> Result<PostPage<Self::Post>> {
+        dapi_post_page(self, &self.0).await
+    }
+
+    /// Retrieves posts matching the configured query, decoding each post
+    /// separately.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::Unauthorized`] if API credentials are missing or invalid.
+    /// Returns [`BooruError::ApiError`] if Rule34 answers with an error page.
+    /// Returns other errors if the request fails or if the response is not a JSON array.
+    async fn get_lenient(&self) -> Result<LenientPosts<Self::Post>> {
+        let text = self.fetch_text(&self.page_query()).await?;
+
+        // Handle empty response (no results)
+        if text.is_empty() {
+            return Ok(LenientPosts::default());
+        }
+
+        let items: Vec<Value> = serde_json::from_str(&text)?;
+        Ok(decode_items(items))
+    }
+}
+
+impl Rule34Client {
+    /// Builds the DAPI query for the configured post search.
+    fn page_query(&self) -> Vec<(&'static str, String)> {
         let builder = &self.0;
-        let url = &builder.url;
         let tag_string = builder.tags.join(" ");
 
         let mut query = vec![
@@ -141,36 +217,87 @@ impl Client for Rule34Client {
             query.push(("user_id", user.clone()));
         }
 
+        query
+    }
+
+    /// Retrieves the comments on a post, oldest first.
+    ///
+    /// Rule34 only serves comments as XML; they are decoded into
+    /// [`Comment`]s. The number of comments is also available without a
+    /// request as [`Rule34Post::comment_count`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::Unauthorized`] if API credentials are missing or
+    /// invalid, [`BooruError::ApiError`] if Rule34 answers with an error
+    /// page, and other errors if the request fails or the response cannot be
+    /// parsed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::rule34::Rule34Client;
+    /// use booru_rs::client::Client;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// let comments = Rule34Client::builder()
+    ///     .set_credentials("your_api_key", "your_user_id")
+    ///     .build()
+    ///     .comments(12345)
+    ///     .await?;
+    ///
+    /// for comment in comments {
+    ///     println!("{}: {}", comment.creator, comment.body);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn comments(&self, post_id: u32) -> Result<Vec<Comment>> {
+        dapi_comments(&self.0, post_id).await.map_err(|error| {
+            // Same quirk as post queries: a 200 response with a plain-text
+            // "Missing authentication" body instead of a 401
+            match error {
+                BooruError::ApiError { body_snippet, .. }
+                    if body_snippet.contains("Missing authentication") =>
+                {
+                    credentials_hint(BooruError::Unauthorized(body_snippet))
+                }
+                error => credentials_hint(error),
+            }
+        })
+    }
+
+    /// Sends a DAPI query and returns the raw response body.
+    async fn fetch_text(&self, query: &[(&str, String)]) -> Result<String> {
+        let builder = &self.0;
+        let url = &builder.url;
+
         let response = builder
-            .client
-            .get(format!("{url}/index.php"))
-            .query(&query)
-            .send()
-            .await?;
-
-        // Check for authentication errors (some APIs may return 401)
-        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
-            return Err(BooruError::Unauthorized(
-                "Rule34 requires API credentials. Use set_credentials(api_key, user_id)".into(),
-            ));
-        }
+            .send(builder.client.get(format!("{url}/index.php")).query(query))
+            .await
+            .map_err(credentials_hint)?;
+
+        let status = response.status();
+        let text = response.text().await?;
 
         // Rule34 API quirk: returns HTTP 200 OK with error message in body instead of 401
         // Example: "Missing authentication. Go to api.rule34.xxx for more information"
-        let text = response.text().await?;
         if text.contains("Missing authentication") {
-            return Err(BooruError::Unauthorized(
-                "Rule34 requires API credentials. Use set_credentials(api_key, user_id)".into(),
-            ));
+            return Err(credentials_hint(BooruError::Unauthorized(text)));
         }
 
-        // Handle empty response (no results)
-        if text.is_empty() || text == "[]" {
-            return Ok(Vec::new());
-        }
+        error_for_markup(status, &text)?;
+        Ok(text)
+    }
+}
 
-        let posts: Vec<Rule34Post> = serde_json::from_str(&text)?;
-        Ok(posts)
+/// Replaces the server's authentication error with a hint on how to fix it.
+fn credentials_hint(error: BooruError) -> BooruError {
+    match error {
+        BooruError::Unauthorized(_) => BooruError::Unauthorized(
+            "Rule34 requires API credentials. Use set_credentials(api_key, user_id)".into(),
+        ),
+        error => error,
     }
 }
 
@@ -191,13 +318,7 @@ impl Autocomplete for Rule34Client {
 
         let response = client.get(url).query(&[("q", query)]).send().await?;
 
-        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
-            return Err(BooruError::Unauthorized(
-                "Rule34 autocomplete request failed".into(),
-            ));
-        }
-
-        let items: Vec<Rule34AutocompleteItem> = response.json().await?;
+        let items: Vec<Rule34AutocompleteItem> = error_for_status(response).await?.json().await?;
 
         Ok(items
             .into_iter()
@@ -1,11 +1,17 @@
 //! Safebooru API client implementation.
 
-use super::{Client, ClientBuilder, shared_client};
+use super::{
+    Client, ClientBuilder, PostPage, PostsById, dapi_post_page, error_for_status,
+    get_by_ids_chunked, get_by_md5_search, id_or_search, post_not_found, random_by_offset,
+    shared_client,
+};
 use crate::autocomplete::{Autocomplete, TagSuggestion};
 use crate::error::{BooruError, Result};
+use crate::model::lenient::{LenientPosts, decode_items};
 use crate::model::safebooru::{SafebooruPost, SafebooruRating};
 
 use serde::Deserialize;
+use serde_json::Value;
 
 /// Client for interacting with the Safebooru API.
 ///
@@ -58,17 +64,15 @@ impl Client for SafebooruClient {
         let url = &builder.url;
 
         let response = builder
-            .client
-            .get(format!("{url}/index.php"))
-            .query(&[
+            .send(builder.client.get(format!("{url}/index.php")).query(&[
                 ("page", "dapi"),
                 ("s", "post"),
                 ("q", "index"),
                 ("id", &id.to_string()),
                 ("json", "1"),
-            ])
-            .send()
-            .await?
+            ]))
+            .await
+            .map_err(post_not_found(id))?
             .json::<Vec<SafebooruPost>>()
             .await?;
 
@@ -78,20 +82,87 @@ impl Client for SafebooruClient {
             .ok_or(BooruError::PostNotFound(id))
     }
 
+    /// Retrieves many posts by ID with `{id:1 ~ id:2 ~ ...}` searches of up
+    /// to 100 IDs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails or if a response cannot be parsed.
+    async fn get_by_ids(&self, ids: &[u32]) -> Result<PostsById<Self::Post>> {
+        get_by_ids_chunked(&self.0, ids, 100, id_or_search).await
+    }
+
+    /// Retrieves the post whose file has the given MD5 hash.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::InvalidTag`] if `md5` is not a valid MD5 hash,
+    /// and other errors if the request fails or the response cannot be parsed.
+    async fn get_by_md5(&self, md5: &str) -> Result<Option<Self::Post>> {
+        get_by_md5_search(&self.0, md5).await
+    }
+
+    /// Retrieves random posts by fetching single posts at random offsets.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails or if a response cannot be parsed.
+    async fn random(&self, count: u32) -> Result<Vec<Self::Post>> {
+        random_by_offset(&self.0, count).await
+    }
+
     /// Retrieves posts matching the configured query.
     ///
     /// # Errors
     ///
     /// Returns an error if the request fails or if the response cannot be parsed.
     async fn get(&self) -> Result<Vec<Self::Post>> {
+        if self.0.lenient {
+            return Ok(self.get_lenient().await?.posts);
+        }
+
+        Ok(self
+            .fetch_page()
+            .await?
+            .json::<Vec<SafebooruPost>>()
+            .await?)
+    }
+
+    /// Retrieves posts matching the configured query, together with the
+    /// total and offset.
+    ///
+    /// Safebooru's JSON API doesn't report the total, so it is fetched with a
+    /// second request for the XML post list.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails or if a response cannot be parsed.
+    async fn get_page(&self) -> Result<PostPage<Self::Post>> {
+        dapi_post_page(self, &self.0).await
+    }
+
+    /// Retrieves posts matching the configured query, decoding each post
+    /// separately.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or if the response is not a JSON array.
+    async fn get_lenient(&self) -> Result<LenientPosts<Self::Post>> {
+        let items = self.fetch_page().await?.json::<Vec<Value>>().await?;
+
+        Ok(decode_items(items))
+    }
+}
+
+impl SafebooruClient {
+    /// Sends the configured post search.
+    async fn fetch_page(&self) -> Result<reqwest::Response> {
         let builder = &self.0;
         let url = &builder.url;
         let tags = builder.tags.join(" ");
 
-        let response = builder
-            .client
-            .get(format!("{url}/index.php"))
-            .query(&[
+        builder
+            .send(builder.client.get(format!("{url}/index.php")).query(&[
                 ("page", "dapi"),
                 ("s", "post"),
                 ("q", "index"),
@@ -99,13 +170,8 @@ impl Client for SafebooruClient {
                 ("limit", &builder.limit.to_string()),
                 ("tags", &tags),
                 ("json", "1"),
-            ])
-            .send()
-            .await?
-            .json::<Vec<SafebooruPost>>()
-            .await?;
-
-        Ok(response)
+            ]))
+            .await
     }
 }
 
@@ -138,6 +204,8 @@ impl Autocomplete for SafebooruClient {
             .get(format!("{}/autocomplete.php", Self::URL))
             .query(&[("q", query)])
             .send()
+            .await?;
+        let response = error_for_status(response)
             .await?
             .json::<Vec<SafebooruAutocompleteItem>>()
             .await?;
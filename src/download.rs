@@ -30,8 +30,9 @@
 //! # }
 //! ```
 
+use crate::circuit::{CircuitBreaker, host_key};
 use crate::error::{BooruError, Result};
-use crate::model::Post;
+use crate::model::{Post, url_extension};
 use std::path::{Path, PathBuf};
 use tokio::io::AsyncWriteExt;
 
@@ -40,10 +41,15 @@ use tokio::io::AsyncWriteExt;
 pub struct DownloadOptions {
     /// Whether to overwrite existing files.
     pub overwrite: bool,
-    /// Custom filename template. Use `{id}`, `{md5}`, `{ext}` as placeholders.
+    /// Custom filename template. Use `{id}`, `{md5}`, `{ext}`, `{index}` as placeholders.
     pub filename_template: Option<String>,
     /// Create subdirectories based on rating.
     pub organize_by_rating: bool,
+    /// Box the downloaded files should fill, as `(width, height)`.
+    ///
+    /// When set, the smallest variant of each post that fills the box is
+    /// downloaded instead of the original (see [`Post::file_url_for_size`]).
+    pub target_size: Option<(u32, u32)>,
 }
 
 impl DownloadOptions {
@@ -60,6 +66,8 @@ impl DownloadOptions {
     /// - `{id}` - Post ID
     /// - `{md5}` - MD5 hash (if available)
     /// - `{ext}` - File extension
+    /// - `{index}` - 1-based position in the batch, zero-padded to the
+    ///   width of the batch size (at least 3 digits)
     #[must_use]
     pub fn filename(mut self, template: impl Into<String>) -> Self {
         self.filename_template = Some(template.into());
@@ -72,6 +80,14 @@ impl DownloadOptions {
         self.organize_by_rating = true;
         self
     }
+
+    /// Download the smallest variant that fills a `width`x`height` box
+    /// instead of the original file, where the site provides variants.
+    #[must_use]
+    pub fn target_size(mut self, width: u32, height: u32) -> Self {
+        self.target_size = Some((width, height));
+        self
+    }
 }
 
 /// Result of a download operation.
@@ -114,12 +130,14 @@ pub type ProgressCallback = Box<dyn Fn(DownloadProgress) + Send + Sync>;
 pub struct Downloader {
     client: reqwest::Client,
     options: DownloadOptions,
+    circuit_breaker: Option<CircuitBreaker>,
 }
 
 impl std::fmt::Debug for Downloader {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Downloader")
             .field("options", &self.options)
+            .field("circuit_breaker", &self.circuit_breaker)
             .finish()
     }
 }
@@ -140,6 +158,7 @@ impl Downloader {
                 .build()
                 .expect("Failed to create HTTP client"),
             options: DownloadOptions::default(),
+            circuit_breaker: None,
         }
     }
 
@@ -149,6 +168,7 @@ impl Downloader {
         Self {
             client,
             options: DownloadOptions::default(),
+            circuit_breaker: None,
         }
     }
 
@@ -168,9 +188,20 @@ impl Downloader {
                 .build()
                 .expect("Failed to create HTTP client"),
             options: self.options,
+            circuit_breaker: self.circuit_breaker,
         }
     }
 
+    /// Sets a circuit breaker for image hosts.
+    ///
+    /// While a host's circuit is open, downloads from it fail immediately
+    /// with [`BooruError::CircuitOpen`].
+    #[must_use]
+    pub fn with_circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.circuit_breaker = Some(breaker);
+        self
+    }
+
     /// Downloads an image from a URL to a directory.
     ///
     /// Returns the path where the file was saved.
@@ -210,13 +241,7 @@ impl Downloader {
         tokio::fs::create_dir_all(dest_dir).await?;
 
         // Download the file
-        let response = self
-            .client
-            .get(url)
-            .send()
-            .await?
-            .error_for_status()
-            .map_err(BooruError::Request)?;
+        let response = fetch(&self.client, self.circuit_breaker.as_ref(), url).await?;
 
         let bytes = response.bytes().await?;
         let size = bytes.len() as u64;
@@ -269,13 +294,7 @@ impl Downloader {
 
         tokio::fs::create_dir_all(dest_dir).await?;
 
-        let response = self
-            .client
-            .get(url)
-            .send()
-            .await?
-            .error_for_status()
-            .map_err(BooruError::Request)?;
+        let response = fetch(&self.client, self.circuit_breaker.as_ref(), url).await?;
 
         let total = response.content_length();
         let mut downloaded: u64 = 0;
@@ -326,11 +345,11 @@ impl Downloader {
     ///
     /// Returns an error if the post has no file URL or the download fails.
     pub async fn download_post(&self, post: &impl Post, dest_dir: &Path) -> Result<DownloadResult> {
-        let url = post
-            .file_url()
+        let url = self
+            .post_url(post)
             .ok_or_else(|| BooruError::InvalidUrl("Post has no file URL".to_string()))?;
 
-        let filename = self.generate_filename(post, url);
+        let filename = self.generate_filename(post, url, Sequence::SINGLE);
         self.download_url(url, dest_dir, Some(&filename)).await
     }
 
@@ -344,11 +363,11 @@ impl Downloader {
     where
         F: Fn(DownloadProgress) + Send,
     {
-        let url = post
-            .file_url()
+        let url = self
+            .post_url(post)
             .ok_or_else(|| BooruError::InvalidUrl("Post has no file URL".to_string()))?;
 
-        let filename = self.generate_filename(post, url);
+        let filename = self.generate_filename(post, url, Sequence::SINGLE);
         self.download_url_with_progress(url, dest_dir, Some(&filename), post.id(), on_progress)
             .await
     }
@@ -361,6 +380,114 @@ impl Downloader {
         posts: &[impl Post + Sync],
         dest_dir: &Path,
         concurrency: usize,
+    ) -> Vec<Result<DownloadResult>> {
+        let sequences = Sequence::consecutive(posts.len());
+        self.download_batch(posts, &sequences, dest_dir, concurrency, None)
+            .await
+    }
+
+    /// Downloads an ordered sequence of posts, such as the pages of a comic.
+    ///
+    /// Unless a filename template is set, files are named by their position
+    /// in the sequence with zero padding (`001.jpg`, `002.png`, ...), so they
+    /// sort in reading order.
+    ///
+    /// Returns results in the same order as the input posts.
+    pub async fn download_sequence(
+        &self,
+        posts: &[impl Post + Sync],
+        dest_dir: &Path,
+        concurrency: usize,
+    ) -> Vec<Result<DownloadResult>> {
+        let sequences = Sequence::consecutive(posts.len());
+        self.download_batch(
+            posts,
+            &sequences,
+            dest_dir,
+            concurrency,
+            Some("{index}.{ext}"),
+        )
+        .await
+    }
+
+    /// Downloads the posts of a Danbooru pool in pool order.
+    ///
+    /// Files are named as in [`download_sequence`](Self::download_sequence),
+    /// by their position in [`DanbooruPool::post_ids`]. Posts that are no
+    /// longer visible leave a gap in the numbering, so every file keeps the
+    /// number of its page.
+    ///
+    /// [`DanbooruPool::post_ids`]: crate::model::danbooru::DanbooruPool::post_ids
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pool's posts cannot be fetched. Errors of
+    /// individual downloads are returned in the result list.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::danbooru::DanbooruClient;
+    /// use booru_rs::download::Downloader;
+    /// use booru_rs::client::Client;
+    /// use std::path::Path;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// let client = DanbooruClient::builder().build();
+    /// let pool = client.pool(1234).await?;
+    ///
+    /// let results = Downloader::new()
+    ///     .download_pool(&client, &pool, Path::new("./comic"), 4)
+    ///     .await?;
+    /// println!("Downloaded {} pages", results.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "danbooru")]
+    pub async fn download_pool(
+        &self,
+        client: &crate::client::danbooru::DanbooruClient,
+        pool: &crate::model::danbooru::DanbooruPool,
+        dest_dir: &Path,
+        concurrency: usize,
+    ) -> Result<Vec<Result<DownloadResult>>> {
+        let posts = client.pool_posts(pool).await?;
+
+        // Posts come in pool order, so each one is the next matching ID
+        let len = pool.post_ids.len();
+        let mut positions = pool.post_ids.iter().enumerate();
+        let sequences: Vec<Sequence> = posts
+            .iter()
+            .map(|post| {
+                let index = positions
+                    .find(|(_, id)| **id == post.id)
+                    .map_or(0, |(index, _)| index);
+                Sequence { index, len }
+            })
+            .collect();
+
+        Ok(self
+            .download_batch(
+                &posts,
+                &sequences,
+                dest_dir,
+                concurrency,
+                Some("{index}.{ext}"),
+            )
+            .await)
+    }
+
+    /// Downloads posts concurrently, falling back to `default_template` for
+    /// filenames when no template is configured.
+    ///
+    /// `sequences` gives the position of each post for `{index}`.
+    async fn download_batch(
+        &self,
+        posts: &[impl Post + Sync],
+        sequences: &[Sequence],
+        dest_dir: &Path,
+        concurrency: usize,
+        default_template: Option<&str>,
     ) -> Vec<Result<DownloadResult>> {
         use std::sync::Arc;
         use tokio::sync::Semaphore;
@@ -368,13 +495,19 @@ impl Downloader {
         let semaphore = Arc::new(Semaphore::new(concurrency));
         let mut handles = Vec::with_capacity(posts.len());
 
-        for post in posts {
+        for (post, &sequence) in posts.iter().zip(sequences) {
             let permit = semaphore.clone().acquire_owned().await.unwrap();
-            let url = post.file_url().map(|s| s.to_string());
+            let url = self.post_url(post).map(|s| s.to_string());
             let id = post.id();
-            let filename = url.as_ref().map(|u| self.generate_filename(post, u));
+            let filename = url.as_ref().map(|u| match default_template {
+                Some(template) if self.options.filename_template.is_none() => {
+                    render_filename(template, post, u, sequence)
+                }
+                _ => self.generate_filename(post, u, sequence),
+            });
             let dest = dest_dir.to_path_buf();
             let client = self.client.clone();
+            let breaker = self.circuit_breaker.clone();
             let options = self.options.clone();
 
             handles.push(tokio::spawn(async move {
@@ -398,12 +531,7 @@ impl Downloader {
 
                 tokio::fs::create_dir_all(&dest).await?;
 
-                let response = client
-                    .get(&url)
-                    .send()
-                    .await?
-                    .error_for_status()
-                    .map_err(BooruError::Request)?;
+                let response = fetch(&client, breaker.as_ref(), &url).await?;
 
                 let bytes = response.bytes().await?;
                 let size = bytes.len() as u64;
@@ -431,27 +559,78 @@ impl Downloader {
         results
     }
 
-    fn generate_filename(&self, post: &impl Post, url: &str) -> String {
-        let ext = url
-            .rsplit('.')
-            .next()
-            .unwrap_or("jpg")
-            .split('?')
-            .next()
-            .unwrap_or("jpg");
-
-        if let Some(template) = &self.options.filename_template {
-            let mut filename = template.clone();
-            filename = filename.replace("{id}", &post.id().to_string());
-            filename = filename.replace("{md5}", post.md5().unwrap_or("unknown"));
-            filename = filename.replace("{ext}", ext);
-            filename
-        } else {
-            format!("{}.{}", post.id(), ext)
+    /// Returns the URL to download for a post, honouring the target size.
+    fn post_url<'a>(&self, post: &'a impl Post) -> Option<&'a str> {
+        match self.options.target_size {
+            Some((width, height)) => post.file_url_for_size(width, height),
+            None => post.file_url(),
+        }
+    }
+
+    fn generate_filename(&self, post: &impl Post, url: &str, sequence: Sequence) -> String {
+        match &self.options.filename_template {
+            Some(template) => render_filename(template, post, url, sequence),
+            None => render_filename("{id}.{ext}", post, url, sequence),
         }
     }
 }
 
+/// Position of a post within a batch download.
+#[derive(Debug, Clone, Copy)]
+struct Sequence {
+    index: usize,
+    len: usize,
+}
+
+impl Sequence {
+    /// The position of a post downloaded on its own.
+    const SINGLE: Self = Self { index: 0, len: 1 };
+
+    /// Returns the positions of a batch of `len` posts, in order.
+    fn consecutive(len: usize) -> Vec<Self> {
+        (0..len).map(|index| Self { index, len }).collect()
+    }
+
+    /// Returns the 1-based position, zero-padded to the width of the batch
+    /// size and at least 3 digits.
+    fn padded(self) -> String {
+        let width = self.len.to_string().len().max(3);
+        format!("{:0width$}", self.index + 1)
+    }
+}
+
+/// Fills in the placeholders of a filename template.
+fn render_filename(template: &str, post: &impl Post, url: &str, sequence: Sequence) -> String {
+    let ext = url_extension(url).unwrap_or("jpg");
+
+    template
+        .replace("{id}", &post.id().to_string())
+        .replace("{md5}", post.md5().unwrap_or("unknown"))
+        .replace("{ext}", ext)
+        .replace("{index}", &sequence.padded())
+}
+
+/// Fetches a URL, going through the circuit breaker if one is configured.
+async fn fetch(
+    client: &reqwest::Client,
+    breaker: Option<&CircuitBreaker>,
+    url: &str,
+) -> Result<reqwest::Response> {
+    let request = client.get(url).build()?;
+    let host = host_key(request.url());
+
+    if let Some(breaker) = breaker {
+        breaker.check(&host)?;
+    }
+
+    let outcome = client.execute(request).await;
+    if let Some(breaker) = breaker {
+        breaker.record_outcome(&host, &outcome);
+    }
+
+    outcome?.error_for_status().map_err(BooruError::Request)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -472,4 +651,37 @@ mod tests {
         assert!(opts.overwrite);
         assert!(opts.filename_template.is_some());
     }
+
+    #[test]
+    fn test_target_size_option() {
+        let opts = DownloadOptions::default().target_size(720, 720);
+        assert_eq!(opts.target_size, Some((720, 720)));
+    }
+
+    #[test]
+    fn test_url_extension() {
+        assert_eq!(
+            url_extension("https://cdn.example.com/a/b.webm"),
+            Some("webm")
+        );
+        assert_eq!(
+            url_extension("https://cdn.example.com/b.png?download=1"),
+            Some("png")
+        );
+        assert_eq!(url_extension("https://cdn.v1.example.com/file"), None);
+    }
+
+    #[test]
+    fn test_sequence_padding() {
+        assert_eq!(Sequence::SINGLE.padded(), "001");
+        assert_eq!(Sequence { index: 9, len: 12 }.padded(), "010");
+        assert_eq!(
+            Sequence {
+                index: 41,
+                len: 1500
+            }
+            .padded(),
+            "0042"
+        );
+    }
 }
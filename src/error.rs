@@ -1,5 +1,13 @@
 //! Error types for the booru-rs library.
 
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+
+/// Maximum number of characters kept in [`BooruError::ApiError::body_snippet`].
+const BODY_SNIPPET_LEN: usize = 200;
+
 /// A specialized `Result` type for booru-rs operations.
 pub type Result<T> = std::result::Result<T, BooruError>;
 
@@ -60,9 +68,68 @@ pub enum BooruError {
         reason: String,
     },
 
+    /// A date was not in the `YYYY-MM-DD` format.
+    #[error("Invalid date '{0}', expected YYYY-MM-DD")]
+    InvalidDate(String),
+
     /// Rate limit exceeded.
     #[error("Rate limit exceeded, please wait before making more requests")]
-    RateLimited,
+    RateLimited {
+        /// How long the server asked us to wait, from the `Retry-After` header.
+        retry_after: Option<Duration>,
+    },
+
+    /// Access to the resource is forbidden (HTTP 403).
+    ///
+    /// The credentials are valid but lack permission for this operation,
+    /// or the request was blocked (e.g. by a CDN challenge).
+    #[error("Access forbidden: {0}")]
+    Forbidden(String),
+
+    /// The server failed to handle the request (HTTP 5xx).
+    #[error("Server error (HTTP {status})")]
+    ServerError {
+        /// The HTTP status code.
+        status: u16,
+    },
+
+    /// The API rejected the request with an error response.
+    ///
+    /// Produced for non-success responses that don't map to a more specific
+    /// variant. The message is extracted from the response body where the
+    /// site provides one (e.g. Danbooru's JSON errors or Gelbooru's XML and
+    /// HTML error pages).
+    #[error("API error (HTTP {status}): {message}")]
+    ApiError {
+        /// The HTTP status code.
+        status: u16,
+        /// The error message reported by the API.
+        message: String,
+        /// The beginning of the response body, for debugging.
+        body_snippet: String,
+    },
+
+    /// The uploaded file already exists as a post.
+    ///
+    /// Returned when a site rejects a new post because a post with the same
+    /// MD5 hash exists.
+    #[error("Duplicate of post #{post_id}")]
+    DuplicatePost {
+        /// The ID of the existing post.
+        post_id: u32,
+    },
+
+    /// The circuit breaker for this host is open.
+    ///
+    /// Requests fail immediately without contacting the server until the
+    /// cool-down period has passed.
+    #[error("Circuit open for {host}, retry in {retry_after:?}")]
+    CircuitOpen {
+        /// The host whose circuit is open.
+        host: String,
+        /// Time remaining until trial requests are allowed again.
+        retry_after: Duration,
+    },
 
     /// I/O error occurred.
     #[error("I/O error: {0}")]
@@ -85,6 +152,232 @@ impl BooruError {
     /// Returns `true` if this error indicates the resource was not found.
     #[must_use]
     pub fn is_not_found(&self) -> bool {
-        matches!(self, Self::PostNotFound(_) | Self::EmptyResponse)
+        matches!(
+            self,
+            Self::PostNotFound(_) | Self::EmptyResponse | Self::ApiError { status: 404, .. }
+        )
+    }
+
+    /// Creates an error from a non-success HTTP response.
+    ///
+    /// - 401 maps to [`Unauthorized`](Self::Unauthorized)
+    /// - 403 maps to [`Forbidden`](Self::Forbidden)
+    /// - 429 maps to [`RateLimited`](Self::RateLimited), honouring `Retry-After`
+    /// - 5xx maps to [`ServerError`](Self::ServerError)
+    /// - anything else (including 404) maps to [`ApiError`](Self::ApiError);
+    ///   every client's `get_by_id` turns a 404 into
+    ///   [`PostNotFound`](Self::PostNotFound)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use booru_rs::error::BooruError;
+    /// use reqwest::StatusCode;
+    /// use reqwest::header::HeaderMap;
+    ///
+    /// let body = r#"{"success":false,"message":"That record was not found."}"#;
+    /// let err = BooruError::from_response(StatusCode::NOT_FOUND, &HeaderMap::new(), body);
+    ///
+    /// assert!(matches!(
+    ///     err,
+    ///     BooruError::ApiError { status: 404, ref message, .. } if message == "That record was not found."
+    /// ));
+    /// ```
+    #[must_use]
+    pub fn from_response(status: StatusCode, headers: &HeaderMap, body: &str) -> Self {
+        let message = extract_error_message(body);
+
+        match status {
+            StatusCode::UNAUTHORIZED => Self::Unauthorized(
+                message.unwrap_or_else(|| "the server rejected the credentials".to_string()),
+            ),
+            StatusCode::FORBIDDEN => Self::Forbidden(
+                message.unwrap_or_else(|| "the server refused the request".to_string()),
+            ),
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited {
+                retry_after: crate::ratelimit::parse_retry_after(headers),
+            },
+            s if s.is_server_error() => Self::ServerError { status: s.as_u16() },
+            s => Self::ApiError {
+                status: s.as_u16(),
+                message: message
+                    .unwrap_or_else(|| s.canonical_reason().unwrap_or("unknown error").to_string()),
+                body_snippet: snippet(body),
+            },
+        }
+    }
+
+    /// Returns the HTTP status code associated with this error, if known.
+    #[must_use]
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::Request(e) => e.status(),
+            Self::RateLimited { .. } => Some(StatusCode::TOO_MANY_REQUESTS),
+            Self::Forbidden(_) => Some(StatusCode::FORBIDDEN),
+            Self::DuplicatePost { .. } => Some(StatusCode::UNPROCESSABLE_ENTITY),
+            Self::ServerError { status } | Self::ApiError { status, .. } => {
+                StatusCode::from_u16(*status).ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns how long the server asked us to wait before retrying, if it did.
+    #[must_use]
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::RateLimited { retry_after } => *retry_after,
+            Self::CircuitOpen { retry_after, .. } => Some(*retry_after),
+            _ => None,
+        }
+    }
+}
+
+/// Extracts a human-readable error message from an error response body.
+///
+/// Understands JSON bodies with a `message`, `reason` or `error` field
+/// (Danbooru returns `{"success":false,"message":...}`), Gelbooru-style XML
+/// (`<response success="false" reason="..."/>`, `<error>...</error>`) and
+/// the `<title>` of HTML error pages.
+fn extract_error_message(body: &str) -> Option<String> {
+    let body = body.trim();
+
+    if let Ok(serde_json::Value::Object(map)) = serde_json::from_str::<serde_json::Value>(body) {
+        return ["message", "reason", "error"]
+            .iter()
+            .find_map(|key| map.get(*key)?.as_str())
+            .map(str::trim)
+            .filter(|m| !m.is_empty())
+            .map(str::to_string);
+    }
+
+    if !body.starts_with('<') {
+        return None;
+    }
+
+    xml_attribute(body, "reason")
+        .or_else(|| xml_element(body, "error"))
+        .or_else(|| xml_element(body, "message"))
+        .or_else(|| xml_element(body, "title"))
+        .or_else(|| xml_element(body, "h1"))
+}
+
+/// Returns the value of the first `name="..."` attribute in `body`.
+fn xml_attribute(body: &str, name: &str) -> Option<String> {
+    let start = body.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = body[start..].find('"')? + start;
+    non_empty(&body[start..end])
+}
+
+/// Returns the text of the first `<name>...</name>` element in `body`.
+fn xml_element(body: &str, name: &str) -> Option<String> {
+    let lower = body.to_ascii_lowercase();
+    let open = lower.find(&format!("<{name}"))?;
+    let start = lower[open..].find('>')? + open + 1;
+    let end = lower[start..].find(&format!("</{name}>"))? + start;
+    non_empty(&body[start..end])
+}
+
+fn non_empty(text: &str) -> Option<String> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+/// Truncates a response body for inclusion in an error.
+fn snippet(body: &str) -> String {
+    let body = body.trim();
+    match body.char_indices().nth(BODY_SNIPPET_LEN) {
+        Some((end, _)) => format!("{}...", &body[..end]),
+        None => body.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(status: u16, body: &str) -> BooruError {
+        BooruError::from_response(
+            StatusCode::from_u16(status).unwrap(),
+            &HeaderMap::new(),
+            body,
+        )
+    }
+
+    #[test]
+    fn test_status_mapping() {
+        assert!(matches!(error(401, ""), BooruError::Unauthorized(_)));
+        assert!(matches!(error(403, ""), BooruError::Forbidden(_)));
+        assert!(matches!(
+            error(429, ""),
+            BooruError::RateLimited { retry_after: None }
+        ));
+        assert!(matches!(
+            error(502, "Bad Gateway"),
+            BooruError::ServerError { status: 502 }
+        ));
+        assert!(matches!(
+            error(404, ""),
+            BooruError::ApiError { status: 404, .. }
+        ));
+        assert!(error(404, "").is_not_found());
+    }
+
+    #[test]
+    fn test_retry_after_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert(reqwest::header::RETRY_AFTER, "7".parse().unwrap());
+
+        let err = BooruError::from_response(StatusCode::TOO_MANY_REQUESTS, &headers, "");
+        assert_eq!(err.retry_after(), Some(Duration::from_secs(7)));
+    }
+
+    #[test]
+    fn test_danbooru_json_message() {
+        let body = r#"{"success":false,"error":"User::PrivilegeError","message":"Access denied"}"#;
+        assert!(matches!(error(403, body), BooruError::Forbidden(ref m) if m == "Access denied"));
+
+        let body = r#"{"success":false,"message":"Tag limit exceeded"}"#;
+        assert!(matches!(
+            error(422, body),
+            BooruError::ApiError { status: 422, ref message, .. } if message == "Tag limit exceeded"
+        ));
+    }
+
+    #[test]
+    fn test_gelbooru_xml_message() {
+        let body = r#"<?xml version="1.0" encoding="UTF-8"?><response success="false" reason="Search error: API limited due to abuse."/>"#;
+        assert!(matches!(
+            error(400, body),
+            BooruError::ApiError { ref message, .. } if message == "Search error: API limited due to abuse."
+        ));
+
+        let body = "<error>Invalid post id</error>";
+        assert!(matches!(
+            error(400, body),
+            BooruError::ApiError { ref message, .. } if message == "Invalid post id"
+        ));
+    }
+
+    #[test]
+    fn test_html_error_page() {
+        let body = "<html><head><TITLE>403 Forbidden</TITLE></head><body>nginx</body></html>";
+        assert!(matches!(error(403, body), BooruError::Forbidden(ref m) if m == "403 Forbidden"));
+    }
+
+    #[test]
+    fn test_body_snippet_truncated() {
+        let body = "x".repeat(1000);
+        match error(400, &body) {
+            BooruError::ApiError {
+                message,
+                body_snippet,
+                ..
+            } => {
+                assert_eq!(message, "Bad Request");
+                assert_eq!(body_snippet.len(), BODY_SNIPPET_LEN + 3);
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
     }
 }
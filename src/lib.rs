@@ -16,9 +16,11 @@
 //! - **Image downloads**: Download images with progress tracking and concurrent downloads
 //! - **Automatic retries**: Transient failures are retried with exponential backoff
 //! - **Rate limiting**: Protect against API throttling
+//! - **Circuit breaking**: Stop hammering hosts that are down
 //! - **Response caching**: Reduce redundant API calls
 //! - **Tag validation**: Catch common mistakes before making requests
 //! - **Tag autocomplete**: Get tag suggestions as users type
+//! - **Reverse lookup**: Find which sites host a file by its MD5 hash
 //!
 //! ## Quick Start
 //!
@@ -91,9 +93,17 @@
 
 pub mod autocomplete;
 pub mod cache;
+pub mod circuit;
 pub mod client;
 pub mod download;
 pub mod error;
+#[cfg(any(
+    feature = "danbooru",
+    feature = "gelbooru",
+    feature = "rule34",
+    feature = "safebooru"
+))]
+pub mod lookup;
 pub mod model;
 pub mod prelude;
 pub mod ratelimit;
@@ -0,0 +1,263 @@
+//! Finding a file on every supported site at once.
+//!
+//! [`Md5Lookup`] searches each enabled site for a file's MD5 hash
+//! concurrently and reports which sites host it.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use booru_rs::lookup::Md5Lookup;
+//! use booru_rs::prelude::*;
+//!
+//! # async fn example() -> Result<()> {
+//! let result = Md5Lookup::new()
+//!     .gelbooru(GelbooruClient::builder().set_credentials("your_api_key", "your_user_id"))
+//!     .find("d41d8cd98f00b204e9800998ecf8427e")
+//!     .await?;
+//!
+//! for found in &result.matches {
+//!     println!("{}: post #{} (source: {:?})", found.site, found.post_id, found.source);
+//! }
+//! for (site, error) in &result.errors {
+//!     eprintln!("{site} failed: {error}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+
+use tokio::task::JoinSet;
+
+use crate::client::{Client, ClientBuilder, normalize_md5};
+use crate::error::{BooruError, Result};
+use crate::model::Post;
+
+#[cfg(feature = "danbooru")]
+use crate::client::DanbooruClient;
+#[cfg(feature = "gelbooru")]
+use crate::client::GelbooruClient;
+#[cfg(feature = "rule34")]
+use crate::client::Rule34Client;
+#[cfg(feature = "safebooru")]
+use crate::client::SafebooruClient;
+
+/// A supported booru site.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Site {
+    /// [Danbooru](https://danbooru.donmai.us)
+    #[cfg(feature = "danbooru")]
+    Danbooru,
+    /// [Gelbooru](https://gelbooru.com)
+    #[cfg(feature = "gelbooru")]
+    Gelbooru,
+    /// [Safebooru](https://safebooru.org)
+    #[cfg(feature = "safebooru")]
+    Safebooru,
+    /// [Rule34](https://rule34.xxx)
+    #[cfg(feature = "rule34")]
+    Rule34,
+}
+
+impl fmt::Display for Site {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match *self {
+            #[cfg(feature = "danbooru")]
+            Self::Danbooru => "Danbooru",
+            #[cfg(feature = "gelbooru")]
+            Self::Gelbooru => "Gelbooru",
+            #[cfg(feature = "safebooru")]
+            Self::Safebooru => "Safebooru",
+            #[cfg(feature = "rule34")]
+            Self::Rule34 => "Rule34",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// A post found by [`Md5Lookup::find`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SiteMatch {
+    /// The site hosting the file
+    pub site: Site,
+    /// The ID of the post on that site
+    pub post_id: u32,
+    /// Source URL given for the post, if any
+    pub source: Option<String>,
+    /// URL of the file on that site, if available
+    pub file_url: Option<String>,
+}
+
+/// The outcome of an [`Md5Lookup`].
+#[derive(Debug, Default)]
+pub struct Md5Matches {
+    /// Sites hosting the file, in [`Site`] order.
+    pub matches: Vec<SiteMatch>,
+    /// Sites that could not be searched, with the reason.
+    pub errors: Vec<(Site, BooruError)>,
+}
+
+impl Md5Matches {
+    /// Returns the match on the given site, if any.
+    #[must_use]
+    pub fn on(&self, site: Site) -> Option<&SiteMatch> {
+        self.matches.iter().find(|found| found.site == site)
+    }
+}
+
+/// Searches every enabled site for a file by its MD5 hash.
+///
+/// By default each site is searched with default client settings. Gelbooru
+/// and Rule34 require API credentials, so pass configured builders for them
+/// (or [`skip`](Self::skip) them); otherwise their
+/// [`BooruError::Unauthorized`] errors are reported in [`Md5Matches::errors`].
+#[derive(Debug, Clone)]
+pub struct Md5Lookup {
+    #[cfg(feature = "danbooru")]
+    danbooru: Option<ClientBuilder<DanbooruClient>>,
+    #[cfg(feature = "gelbooru")]
+    gelbooru: Option<ClientBuilder<GelbooruClient>>,
+    #[cfg(feature = "safebooru")]
+    safebooru: Option<ClientBuilder<SafebooruClient>>,
+    #[cfg(feature = "rule34")]
+    rule34: Option<ClientBuilder<Rule34Client>>,
+}
+
+impl Md5Lookup {
+    /// Creates a lookup of every enabled site with default settings.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            #[cfg(feature = "danbooru")]
+            danbooru: Some(DanbooruClient::builder()),
+            #[cfg(feature = "gelbooru")]
+            gelbooru: Some(GelbooruClient::builder()),
+            #[cfg(feature = "safebooru")]
+            safebooru: Some(SafebooruClient::builder()),
+            #[cfg(feature = "rule34")]
+            rule34: Some(Rule34Client::builder()),
+        }
+    }
+
+    /// Searches Danbooru with the given builder's settings.
+    #[cfg(feature = "danbooru")]
+    #[must_use]
+    pub fn danbooru(mut self, builder: ClientBuilder<DanbooruClient>) -> Self {
+        self.danbooru = Some(builder);
+        self
+    }
+
+    /// Searches Gelbooru with the given builder's settings.
+    #[cfg(feature = "gelbooru")]
+    #[must_use]
+    pub fn gelbooru(mut self, builder: ClientBuilder<GelbooruClient>) -> Self {
+        self.gelbooru = Some(builder);
+        self
+    }
+
+    /// Searches Safebooru with the given builder's settings.
+    #[cfg(feature = "safebooru")]
+    #[must_use]
+    pub fn safebooru(mut self, builder: ClientBuilder<SafebooruClient>) -> Self {
+        self.safebooru = Some(builder);
+        self
+    }
+
+    /// Searches Rule34 with the given builder's settings.
+    #[cfg(feature = "rule34")]
+    #[must_use]
+    pub fn rule34(mut self, builder: ClientBuilder<Rule34Client>) -> Self {
+        self.rule34 = Some(builder);
+        self
+    }
+
+    /// Leaves a site out of the lookup.
+    #[must_use]
+    pub fn skip(mut self, site: Site) -> Self {
+        match site {
+            #[cfg(feature = "danbooru")]
+            Site::Danbooru => self.danbooru = None,
+            #[cfg(feature = "gelbooru")]
+            Site::Gelbooru => self.gelbooru = None,
+            #[cfg(feature = "safebooru")]
+            Site::Safebooru => self.safebooru = None,
+            #[cfg(feature = "rule34")]
+            Site::Rule34 => self.rule34 = None,
+        }
+        self
+    }
+
+    /// Searches the sites concurrently for a file with the given MD5 hash.
+    ///
+    /// A failing site does not fail the lookup; its error is reported in
+    /// [`Md5Matches::errors`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BooruError::InvalidTag`] if `md5` is not 32 hexadecimal
+    /// characters.
+    pub async fn find(&self, md5: &str) -> Result<Md5Matches> {
+        let md5 = normalize_md5(md5)?;
+        let mut tasks = JoinSet::new();
+
+        #[cfg(feature = "danbooru")]
+        if let Some(builder) = &self.danbooru {
+            tasks.spawn(find_on(Site::Danbooru, builder.clone(), md5.clone()));
+        }
+        #[cfg(feature = "gelbooru")]
+        if let Some(builder) = &self.gelbooru {
+            tasks.spawn(find_on(Site::Gelbooru, builder.clone(), md5.clone()));
+        }
+        #[cfg(feature = "safebooru")]
+        if let Some(builder) = &self.safebooru {
+            tasks.spawn(find_on(Site::Safebooru, builder.clone(), md5.clone()));
+        }
+        #[cfg(feature = "rule34")]
+        if let Some(builder) = &self.rule34 {
+            tasks.spawn(find_on(Site::Rule34, builder.clone(), md5.clone()));
+        }
+
+        let mut result = Md5Matches::default();
+        while let Some(joined) = tasks.join_next().await {
+            let (site, outcome) = match joined {
+                Ok(done) => done,
+                Err(error) => std::panic::resume_unwind(error.into_panic()),
+            };
+            match outcome {
+                Ok(Some(found)) => result.matches.push(found),
+                Ok(None) => {}
+                Err(error) => result.errors.push((site, error)),
+            }
+        }
+
+        result.matches.sort_by_key(|found| found.site);
+        result.errors.sort_by_key(|(site, _)| *site);
+
+        Ok(result)
+    }
+}
+
+impl Default for Md5Lookup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Searches one site for the file.
+async fn find_on<T: Client + 'static>(
+    site: Site,
+    builder: ClientBuilder<T>,
+    md5: String,
+) -> (Site, Result<Option<SiteMatch>>) {
+    let outcome = builder.build().get_by_md5(&md5).await.map(|post| {
+        post.map(|post| SiteMatch {
+            site,
+            post_id: post.id(),
+            source: post.source().map(String::from),
+            file_url: post.file_url().map(String::from),
+        })
+    });
+
+    (site, outcome)
+}
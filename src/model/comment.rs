@@ -0,0 +1,89 @@
+//! Models for comments on Gelbooru-based sites.
+//!
+//! Gelbooru and Rule34 only serve comments as XML
+//! (`page=dapi&s=comment&q=index`), so the `<comment>` elements are
+//! converted to JSON objects and decoded like every other model.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::{lenient, xml};
+
+/// A comment on a Gelbooru or Rule34 post.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Comment {
+    /// The ID of the comment
+    #[serde(deserialize_with = "lenient::number")]
+    pub id: u32,
+    /// ID of the commented post
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub post_id: u32,
+    /// Comment text
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub body: String,
+    /// Name of the comment's author
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub creator: String,
+    /// ID of the comment's author
+    #[serde(default, deserialize_with = "lenient::optional_number")]
+    pub creator_id: Option<u32>,
+    /// Datestamp of the comment
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub created_at: String,
+    /// Any other attributes of the `<comment>` element.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Decodes a DAPI comment list such as
+/// `<comments type="array"><comment id="1" post_id="2" body="Nice"/></comments>`.
+///
+/// Returns `None` if the document is not a comment list, e.g. an error
+/// response.
+pub(crate) fn parse_comment_list(xml: &str) -> Option<serde_json::Result<Vec<Comment>>> {
+    let list = match xml::parse_list(xml, "comments", "comment")? {
+        Ok(list) => list,
+        Err(error) => return Some(Err(error)),
+    };
+
+    Some(list.items.into_iter().map(serde_json::from_value).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_comment_list() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <comments type="array">
+                <comment created_at="2024-01-02 03:04" post_id="10" body="Love the &quot;colors&quot; &amp; lines" creator="someone" id="5" creator_id="42"/>
+                <comment created_at="2024-01-03 00:00" post_id="10" body="&hearts;" creator="Anonymous" id="6" creator_id=""/>
+            </comments>"#;
+
+        let comments = parse_comment_list(xml).unwrap().unwrap();
+
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0].id, 5);
+        assert_eq!(comments[0].post_id, 10);
+        assert_eq!(comments[0].body, r#"Love the "colors" & lines"#);
+        assert_eq!(comments[0].creator_id, Some(42));
+        assert_eq!(comments[1].body, "&hearts;");
+        assert_eq!(comments[1].creator_id, None);
+    }
+
+    #[test]
+    fn test_parse_empty_comment_list() {
+        let comments = parse_comment_list(r#"<comments type="array"></comments>"#);
+
+        assert!(comments.unwrap().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_non_comment_document() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><response success="false" reason="Missing authentication"/>"#;
+
+        assert!(parse_comment_list(xml).is_none());
+        assert!(parse_comment_list("Missing authentication").is_none());
+    }
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::model::lenient;
+
+/// An artist record from Danbooru.
+///
+/// Artist records belong to artist tags and list the artist's known
+/// websites and aliases.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DanbooruArtist {
+    /// The ID of the artist
+    pub id: u32,
+    /// Name of the artist tag
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub name: String,
+    /// Name of the circle or group the artist belongs to
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub group_name: String,
+    /// Other names the artist is known by
+    #[serde(default)]
+    pub other_names: Vec<String>,
+    /// Whether the artist has been banned from the site
+    #[serde(default, deserialize_with = "lenient::boolean")]
+    pub is_banned: bool,
+    /// Whether the artist record has been deleted
+    #[serde(default, deserialize_with = "lenient::boolean")]
+    pub is_deleted: bool,
+    /// The artist's known websites
+    #[serde(default)]
+    pub urls: Vec<DanbooruArtistUrl>,
+    /// Datestamp of the record's creation
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub created_at: String,
+    /// Datestamp of the record's last update
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub updated_at: String,
+    /// Any other fields of the artist.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl DanbooruArtist {
+    /// Returns the URLs of the artist's websites that are still active.
+    pub fn active_urls(&self) -> impl Iterator<Item = &str> {
+        self.urls
+            .iter()
+            .filter(|url| url.is_active)
+            .map(|url| url.url.as_str())
+    }
+}
+
+/// A website of a Danbooru artist.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DanbooruArtistUrl {
+    /// The ID of the artist URL
+    pub id: u32,
+    /// ID of the artist the URL belongs to
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub artist_id: u32,
+    /// The URL as entered
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub url: String,
+    /// Whether the site is still online and in use by the artist
+    #[serde(default, deserialize_with = "lenient::boolean")]
+    pub is_active: bool,
+    /// Any other fields of the URL, such as `created_at`.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::model::lenient;
+
+/// A comment on a Danbooru post.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DanbooruComment {
+    /// The ID of the comment
+    pub id: u32,
+    /// ID of the post the comment is on
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub post_id: u32,
+    /// ID of the user who wrote the comment
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub creator_id: u32,
+    /// ID of the user who last edited the comment
+    #[serde(default, deserialize_with = "lenient::optional_number")]
+    pub updater_id: Option<u32>,
+    /// Comment text (DText markup)
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub body: String,
+    /// Sum of the comment's votes
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub score: i32,
+    /// Whether the comment has been deleted
+    #[serde(default, deserialize_with = "lenient::boolean")]
+    pub is_deleted: bool,
+    /// Whether the comment is pinned by a moderator
+    #[serde(default, deserialize_with = "lenient::boolean")]
+    pub is_sticky: bool,
+    /// Whether posting the comment left the post's bump date unchanged
+    #[serde(default, deserialize_with = "lenient::boolean")]
+    pub do_not_bump_post: bool,
+    /// Datestamp of the comment's creation
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub created_at: String,
+    /// Datestamp of the comment's last update
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub updated_at: String,
+    /// Any other fields of the comment.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
@@ -0,0 +1,19 @@
+use core::fmt;
+
+/// Time span covered by a Danbooru explore feed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DanbooruExploreScale {
+    /// The 24 hours of the given date
+    Day,
+    /// The week ending on the given date
+    Week,
+    /// The month ending on the given date
+    Month,
+}
+
+impl fmt::Display for DanbooruExploreScale {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let lowercase_tag = format!("{:?}", self).to_lowercase();
+        write!(f, "{lowercase_tag}")
+    }
+}
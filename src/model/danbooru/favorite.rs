@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::model::lenient;
+
+/// A favorite from Danbooru, linking a user to a post.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DanbooruFavorite {
+    /// The ID of the favorite
+    pub id: u32,
+    /// ID of the user who favorited the post
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub user_id: u32,
+    /// ID of the favorited post
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub post_id: u32,
+    /// Any other fields of the favorite.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::model::{MediaType, lenient};
+
+/// The file behind a Danbooru post, with its resized variants.
+///
+/// Included in post responses as `media_asset` by newer Danbooru versions.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DanbooruMediaAsset {
+    /// The ID of the media asset
+    pub id: u32,
+    /// MD5 hash of the original file
+    #[serde(default)]
+    pub md5: Option<String>,
+    /// Extension of the original file (e.g. `jpg`, `mp4`, `zip` for ugoira)
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub file_ext: String,
+    /// Size of the original file in bytes
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub file_size: u64,
+    /// Width of the original file
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub image_width: u32,
+    /// Height of the original file
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub image_height: u32,
+    /// Length in seconds, for videos and ugoira
+    #[serde(default, deserialize_with = "lenient::optional_number")]
+    pub duration: Option<f64>,
+    /// Hash of the decoded pixels, identical for re-encodes of the same image
+    #[serde(default)]
+    pub pixel_hash: Option<String>,
+    /// Processing status such as `active`, `processing` or `failed`
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub status: String,
+    /// Whether the file is visible to everyone
+    #[serde(default, deserialize_with = "lenient::boolean")]
+    pub is_public: bool,
+    /// Available versions of the file, smallest first
+    #[serde(default)]
+    pub variants: Vec<DanbooruMediaVariant>,
+    /// Datestamp of the asset's creation
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub created_at: String,
+    /// Datestamp of the asset's last update
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub updated_at: String,
+    /// Any other fields of the media asset, such as `file_key`.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl DanbooruMediaAsset {
+    /// Returns the kind of media of the original file.
+    #[must_use]
+    pub fn media_type(&self) -> MediaType {
+        MediaType::from_extension(&self.file_ext)
+    }
+
+    /// Returns the variant of the given type (e.g. `"720x720"` or `"original"`).
+    #[must_use]
+    pub fn variant(&self, variant_type: &str) -> Option<&DanbooruMediaVariant> {
+        self.variants
+            .iter()
+            .find(|variant| variant.variant_type == variant_type)
+    }
+
+    /// Returns the smallest variant that fills a `width`x`height` box
+    /// without upscaling, or the largest variant if none does.
+    ///
+    /// For animated media only animated variants are considered, so a
+    /// video never resolves to a still preview.
+    #[must_use]
+    pub fn best_variant(&self, width: u32, height: u32) -> Option<&DanbooruMediaVariant> {
+        let animated = self.media_type().is_animated();
+        let mut candidates: Vec<&DanbooruMediaVariant> = self
+            .variants
+            .iter()
+            .filter(|variant| !animated || variant.media_type().is_animated())
+            .collect();
+        candidates.sort_by_key(|variant| u64::from(variant.width) * u64::from(variant.height));
+
+        candidates
+            .iter()
+            .find(|variant| variant.width >= width || variant.height >= height)
+            .or(candidates.last())
+            .copied()
+    }
+}
+
+/// A version of a Danbooru media asset, such as a thumbnail or sample.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DanbooruMediaVariant {
+    /// Variant type: `180x180`, `360x360`, `720x720`, `sample` or `original`
+    #[serde(rename = "type", default, deserialize_with = "lenient::string")]
+    pub variant_type: String,
+    /// URL of the file
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub url: String,
+    /// Width of the file
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub width: u32,
+    /// Height of the file
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub height: u32,
+    /// Extension of the file (e.g. `webp`, `avif`, `jpg`)
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub file_ext: String,
+    /// Any other fields of the variant.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl DanbooruMediaVariant {
+    /// Returns the kind of media of this variant.
+    #[must_use]
+    pub fn media_type(&self) -> MediaType {
+        MediaType::from_extension(&self.file_ext)
+    }
+}
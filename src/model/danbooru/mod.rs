@@ -5,6 +5,35 @@
 
 use core::fmt;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+mod artist;
+mod comment;
+mod explore;
+mod favorite;
+mod media_asset;
+mod note;
+mod pool;
+mod post_version;
+mod saved_search;
+mod tag;
+mod upload;
+mod vote;
+mod wiki;
+
+pub use artist::*;
+pub use comment::*;
+pub use explore::*;
+pub use favorite::*;
+pub use media_asset::*;
+pub use note::*;
+pub use pool::*;
+pub use post_version::*;
+pub use saved_search::*;
+pub use tag::*;
+pub use upload::*;
+pub use vote::*;
+pub use wiki::*;
 
 /// A post from Danbooru.
 ///
@@ -55,6 +84,22 @@ pub struct DanbooruPost {
     pub is_flagged: bool,
     pub is_pending: bool,
     pub bit_flags: u32,
+    /// The post's file and its resized variants, on newer Danbooru versions
+    #[serde(default)]
+    pub media_asset: Option<DanbooruMediaAsset>,
+    /// Other fields Danbooru sends, such as `tag_count` and `is_favorited`.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl DanbooruPost {
+    /// Returns `true` if the post has ever had notes (e.g. translations).
+    ///
+    /// Fetch them with [`DanbooruClient::notes`](crate::danbooru::DanbooruClient::notes).
+    #[must_use]
+    pub fn has_notes(&self) -> bool {
+        self.last_noted_at.is_some()
+    }
 }
 
 /// Post rating classification.
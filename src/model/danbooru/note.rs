@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::model::lenient;
+
+/// A note from Danbooru.
+///
+/// Notes are text boxes positioned over a post's image, most often used for
+/// translations. Coordinates are in pixels of the original image.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DanbooruNote {
+    /// The ID of the note
+    pub id: u32,
+    /// ID of the post the note is on
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub post_id: u32,
+    /// Distance of the box from the left edge of the image
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub x: u32,
+    /// Distance of the box from the top edge of the image
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub y: u32,
+    /// Width of the box
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub width: u32,
+    /// Height of the box
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub height: u32,
+    /// Note text (a limited subset of HTML)
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub body: String,
+    /// Whether the note is shown; deleted notes are inactive
+    #[serde(default, deserialize_with = "lenient::boolean")]
+    pub is_active: bool,
+    /// Number of times the note has been edited
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub version: u32,
+    /// Datestamp of the note's creation
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub created_at: String,
+    /// Datestamp of the note's last update
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub updated_at: String,
+    /// Any other fields of the note.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
@@ -0,0 +1,90 @@
+use core::fmt;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::model::lenient;
+
+/// A pool from Danbooru.
+///
+/// Pools group posts in a fixed order, for example the pages of a comic
+/// (`series`) or a themed set of images (`collection`).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DanbooruPool {
+    /// The ID of the pool
+    pub id: u32,
+    /// Pool name, with underscores instead of spaces
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub name: String,
+    /// Pool description (DText markup)
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub description: String,
+    /// IDs of the member posts, in pool order
+    #[serde(default)]
+    pub post_ids: Vec<u32>,
+    /// Number of posts in the pool
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub post_count: u32,
+    /// Pool category
+    pub category: DanbooruPoolCategory,
+    /// Whether the pool has been deleted
+    #[serde(default, deserialize_with = "lenient::boolean")]
+    pub is_deleted: bool,
+    /// Datestamp of the pool's creation
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub created_at: String,
+    /// Datestamp of the pool's last update
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub updated_at: String,
+    /// Any other fields of the pool.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl DanbooruPool {
+    /// Returns the pool name with underscores replaced by spaces.
+    #[must_use]
+    pub fn display_name(&self) -> String {
+        self.name.replace('_', " ")
+    }
+}
+
+/// Pool category on Danbooru.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum DanbooruPoolCategory {
+    /// Ordered posts, such as the pages of a comic
+    Series,
+    /// Unordered posts sharing a theme
+    Collection,
+}
+
+impl fmt::Display for DanbooruPoolCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let lowercase_tag = format!("{:?}", self).to_lowercase();
+        write!(f, "{lowercase_tag}")
+    }
+}
+
+/// Sort order for Danbooru pool searches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DanbooruPoolOrder {
+    /// Alphabetical by name
+    Name,
+    /// Newest first
+    CreatedAt,
+    /// Most recently updated first
+    UpdatedAt,
+    /// Most posts first
+    PostCount,
+}
+
+impl fmt::Display for DanbooruPoolOrder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Name => "name",
+            Self::CreatedAt => "created_at",
+            Self::UpdatedAt => "updated_at",
+            Self::PostCount => "post_count",
+        })
+    }
+}
@@ -0,0 +1,298 @@
+use std::collections::BTreeSet;
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::DanbooruRating;
+use crate::model::lenient;
+
+/// A revision of a Danbooru post, from `/post_versions.json`.
+///
+/// Each version records the changes one edit made to a post's tags,
+/// rating, source and parent.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DanbooruPostVersion {
+    /// The ID of the version
+    pub id: u32,
+    /// ID of the post that was edited
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub post_id: u32,
+    /// Revision number of the post, starting at 1
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub version: u32,
+    /// Full space-separated tag string after the edit
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub tags: String,
+    /// Tags added by the edit
+    #[serde(default)]
+    pub added_tags: Vec<String>,
+    /// Tags removed by the edit
+    #[serde(default)]
+    pub removed_tags: Vec<String>,
+    /// Rating after the edit
+    #[serde(default)]
+    pub rating: Option<DanbooruRating>,
+    /// Whether the edit changed the rating
+    #[serde(default, deserialize_with = "lenient::boolean")]
+    pub rating_changed: bool,
+    /// Source after the edit
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub source: String,
+    /// Whether the edit changed the source
+    #[serde(default, deserialize_with = "lenient::boolean")]
+    pub source_changed: bool,
+    /// Parent post ID after the edit
+    #[serde(default, deserialize_with = "lenient::optional_number")]
+    pub parent_id: Option<u32>,
+    /// Whether the edit changed the parent
+    #[serde(default, deserialize_with = "lenient::boolean")]
+    pub parent_changed: bool,
+    /// ID of the user who made the edit
+    #[serde(default, deserialize_with = "lenient::optional_number")]
+    pub updater_id: Option<u32>,
+    /// Datestamp of the edit
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub updated_at: String,
+    /// Any other fields of the version, such as `unchanged_tags`.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl DanbooruPostVersion {
+    /// Returns the time of the edit, or `None` if `updated_at` is not a
+    /// valid ISO 8601 timestamp.
+    #[must_use]
+    pub fn updated_time(&self) -> Option<SystemTime> {
+        parse_timestamp(&self.updated_at)
+    }
+}
+
+/// The version history of a single Danbooru post.
+///
+/// Replays the tag changes of each [`DanbooruPostVersion`] to reconstruct a
+/// post's tags at any earlier version or point in time.
+///
+/// # Example
+///
+/// ```
+/// use booru_rs::danbooru::{DanbooruPostHistory, DanbooruPostVersion};
+///
+/// let versions: Vec<DanbooruPostVersion> = serde_json::from_str(r#"[
+///     {"id": 2, "post_id": 1, "version": 2, "added_tags": ["cat_ears"], "removed_tags": ["dog_ears"]},
+///     {"id": 1, "post_id": 1, "version": 1, "added_tags": ["1girl", "dog_ears"]}
+/// ]"#).unwrap();
+///
+/// let history = DanbooruPostHistory::new(versions);
+///
+/// assert_eq!(history.tags_at_version(1), ["1girl", "dog_ears"].map(String::from).into());
+/// assert_eq!(history.tags_at_version(2), ["1girl", "cat_ears"].map(String::from).into());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DanbooruPostHistory {
+    versions: Vec<DanbooruPostVersion>,
+}
+
+impl DanbooruPostHistory {
+    /// Creates a history from a post's versions, in any order.
+    #[must_use]
+    pub fn new(mut versions: Vec<DanbooruPostVersion>) -> Self {
+        versions.sort_by_key(|version| version.version);
+        Self { versions }
+    }
+
+    /// Returns the versions, oldest first.
+    #[must_use]
+    pub fn versions(&self) -> &[DanbooruPostVersion] {
+        &self.versions
+    }
+
+    /// Returns the tags the post had after the given revision.
+    ///
+    /// Revisions missing from the history are skipped, so the result is
+    /// only exact if every version up to `version` is present.
+    #[must_use]
+    pub fn tags_at_version(&self, version: u32) -> BTreeSet<String> {
+        replay(
+            self.versions
+                .iter()
+                .take_while(|entry| entry.version <= version),
+        )
+    }
+
+    /// Returns the tags the post had at a point in time.
+    ///
+    /// Returns an empty set for times before the first version. Versions
+    /// with an unparseable timestamp are ignored.
+    #[must_use]
+    pub fn tags_at(&self, time: SystemTime) -> BTreeSet<String> {
+        replay(
+            self.versions
+                .iter()
+                .filter(|entry| entry.updated_time().is_some_and(|updated| updated <= time)),
+        )
+    }
+
+    /// Returns the rating the post had after the given revision.
+    #[must_use]
+    pub fn rating_at_version(&self, version: u32) -> Option<DanbooruRating> {
+        self.versions
+            .iter()
+            .take_while(|entry| entry.version <= version)
+            .filter_map(|entry| entry.rating)
+            .last()
+    }
+}
+
+/// Applies the tag changes of each version in turn.
+fn replay<'a>(versions: impl Iterator<Item = &'a DanbooruPostVersion>) -> BTreeSet<String> {
+    let mut tags = BTreeSet::new();
+
+    for version in versions {
+        for tag in &version.removed_tags {
+            tags.remove(tag);
+        }
+        tags.extend(version.added_tags.iter().cloned());
+    }
+
+    tags
+}
+
+/// Parses an ISO 8601 timestamp such as `2024-03-01T12:34:56.789-05:00`.
+fn parse_timestamp(timestamp: &str) -> Option<SystemTime> {
+    let (date, time) = timestamp.split_once('T')?;
+
+    let mut date_parts = date.splitn(3, '-').map(str::parse::<i64>);
+    let (year, month, day) = (
+        date_parts.next()?.ok()?,
+        date_parts.next()?.ok()?,
+        date_parts.next()?.ok()?,
+    );
+    if !(0..=9999).contains(&year) || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    let (clock, offset_seconds) = if let Some(clock) = time.strip_suffix('Z') {
+        (clock, 0)
+    } else {
+        let split = time.rfind(['+', '-'])?;
+        let (clock, offset) = time.split_at(split);
+        let sign = if offset.starts_with('-') { -1 } else { 1 };
+        let (hours, minutes) = offset[1..].split_once(':')?;
+        let (hours, minutes) = (hours.parse::<i64>().ok()?, minutes.parse::<i64>().ok()?);
+        if !(0..=23).contains(&hours) || !(0..=59).contains(&minutes) {
+            return None;
+        }
+        (clock, sign * (hours * 3600 + minutes * 60))
+    };
+
+    let (clock, fraction) = clock.split_once('.').unwrap_or((clock, ""));
+    let mut clock_parts = clock.splitn(3, ':').map(str::parse::<i64>);
+    let (hour, minute, second) = (
+        clock_parts.next()?.ok()?,
+        clock_parts.next()?.ok()?,
+        clock_parts.next()?.ok()?,
+    );
+    if !(0..=23).contains(&hour) || !(0..=59).contains(&minute) || !(0..=59).contains(&second) {
+        return None;
+    }
+
+    let nanos = if fraction.is_empty() {
+        0
+    } else if !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    } else {
+        let digits = &fraction[..fraction.len().min(9)];
+        digits.parse::<u32>().ok()? * 10u32.pow(9 - digits.len() as u32)
+    };
+
+    let seconds = days_from_civil(year, month, day) * 86_400 + hour * 3600 + minute * 60 + second
+        - offset_seconds;
+
+    let seconds = u64::try_from(seconds).ok()?;
+    Some(SystemTime::UNIX_EPOCH + Duration::new(seconds, nanos))
+}
+
+/// Returns the number of days between 1970-01-01 and the given date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn version(
+        number: u32,
+        updated_at: &str,
+        added: &[&str],
+        removed: &[&str],
+    ) -> DanbooruPostVersion {
+        serde_json::from_value(json!({
+            "id": number,
+            "post_id": 1,
+            "version": number,
+            "added_tags": added,
+            "removed_tags": removed,
+            "updated_at": updated_at,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        let expected = SystemTime::UNIX_EPOCH + Duration::new(1_709_314_496, 789_000_000);
+
+        assert_eq!(
+            parse_timestamp("2024-03-01T12:34:56.789-05:00"),
+            Some(expected)
+        );
+        assert_eq!(parse_timestamp("2024-03-01T17:34:56.789Z"), Some(expected));
+        assert_eq!(
+            parse_timestamp("1970-01-01T00:00:00+00:00"),
+            Some(SystemTime::UNIX_EPOCH)
+        );
+        assert_eq!(parse_timestamp("yesterday"), None);
+    }
+
+    #[test]
+    fn test_parse_timestamp_rejects_out_of_range_values() {
+        assert_eq!(parse_timestamp("2024-13-45T99:99:99Z"), None);
+        assert_eq!(parse_timestamp("2024-03-01T12:34:56.7é8Z"), None);
+        assert_eq!(parse_timestamp("9223372036854775807-01-01T00:00:00Z"), None);
+        assert_eq!(parse_timestamp("2024-03-01T12:34:56+99999:00"), None);
+    }
+
+    #[test]
+    fn test_tags_at_time() {
+        let history = DanbooruPostHistory::new(vec![
+            version(3, "2024-01-03T00:00:00.000-05:00", &["smile"], &[]),
+            version(1, "2024-01-01T00:00:00.000-05:00", &["1girl", "solo"], &[]),
+            version(
+                2,
+                "2024-01-02T00:00:00.000-05:00",
+                &["2girls"],
+                &["1girl", "solo"],
+            ),
+        ]);
+
+        let at = |timestamp| history.tags_at(parse_timestamp(timestamp).unwrap());
+
+        assert!(at("2023-12-31T00:00:00Z").is_empty());
+        assert_eq!(
+            at("2024-01-01T12:00:00Z"),
+            BTreeSet::from(["1girl".into(), "solo".into()])
+        );
+        assert_eq!(
+            at("2024-01-02T12:00:00Z"),
+            BTreeSet::from(["2girls".into()])
+        );
+        assert_eq!(history.tags_at_version(3), at("2025-01-01T00:00:00Z"));
+    }
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::model::lenient;
+
+/// A saved search of a Danbooru user.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DanbooruSavedSearch {
+    /// The ID of the saved search
+    pub id: u32,
+    /// ID of the user who owns the saved search
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub user_id: u32,
+    /// The tag search, e.g. `"cat_ears rating:g"`
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub query: String,
+    /// Labels used to group saved searches
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Datestamp of the saved search's creation
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub created_at: String,
+    /// Datestamp of the saved search's last update
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub updated_at: String,
+    /// Any other fields of the saved search.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl DanbooruSavedSearch {
+    /// Returns the individual search terms of the query.
+    pub fn terms(&self) -> impl Iterator<Item = &str> {
+        self.query.split_whitespace()
+    }
+}
@@ -0,0 +1,229 @@
+use core::fmt;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::autocomplete::TagSuggestion;
+use crate::model::lenient;
+
+/// A tag from Danbooru's `/tags.json` endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DanbooruTag {
+    /// The ID of the tag
+    pub id: u32,
+    /// Tag name (with underscores)
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub name: String,
+    /// Number of posts with this tag
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub post_count: u32,
+    /// Category ID (see [`category`](Self::category))
+    #[serde(rename = "category", default, deserialize_with = "lenient::number")]
+    pub category_id: u8,
+    /// Whether the tag has been deprecated in favour of another
+    #[serde(default, deserialize_with = "lenient::boolean")]
+    pub is_deprecated: bool,
+    /// Individual words of the tag name, used for word-based search
+    #[serde(default)]
+    pub words: Vec<String>,
+    /// Datestamp of the tag's creation
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub created_at: String,
+    /// Datestamp of the tag's last update
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub updated_at: String,
+    /// Any other fields of the tag.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl DanbooruTag {
+    /// Returns the tag's category, or `None` if the ID is not a known category.
+    #[must_use]
+    pub fn category(&self) -> Option<DanbooruTagCategory> {
+        DanbooruTagCategory::from_id(self.category_id)
+    }
+
+    /// Returns the name of the tag's category (e.g. `"artist"`).
+    #[must_use]
+    pub fn category_name(&self) -> &'static str {
+        crate::autocomplete::category_name(self.category_id)
+    }
+}
+
+/// Tag category on Danbooru.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(into = "u8", try_from = "u8")]
+pub enum DanbooruTagCategory {
+    General,
+    Artist,
+    Copyright,
+    Character,
+    Meta,
+}
+
+impl DanbooruTagCategory {
+    /// Returns the numeric ID Danbooru uses for this category.
+    #[must_use]
+    pub fn id(self) -> u8 {
+        match self {
+            Self::General => 0,
+            Self::Artist => 1,
+            Self::Copyright => 3,
+            Self::Character => 4,
+            Self::Meta => 5,
+        }
+    }
+
+    /// Returns the category for a numeric ID, if it is known.
+    #[must_use]
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Self::General),
+            1 => Some(Self::Artist),
+            3 => Some(Self::Copyright),
+            4 => Some(Self::Character),
+            5 => Some(Self::Meta),
+            _ => None,
+        }
+    }
+}
+
+impl From<DanbooruTagCategory> for u8 {
+    fn from(category: DanbooruTagCategory) -> u8 {
+        category.id()
+    }
+}
+
+impl TryFrom<u8> for DanbooruTagCategory {
+    type Error = String;
+
+    fn try_from(id: u8) -> Result<Self, Self::Error> {
+        Self::from_id(id).ok_or_else(|| format!("unknown tag category: {id}"))
+    }
+}
+
+impl fmt::Display for DanbooruTagCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(crate::autocomplete::category_name(self.id()))
+    }
+}
+
+/// Sort order for Danbooru tag searches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DanbooruTagOrder {
+    /// Alphabetical by name
+    Name,
+    /// Newest first
+    Date,
+    /// Most posts first
+    Count,
+    /// Most similar to the searched name first
+    Similarity,
+}
+
+impl fmt::Display for DanbooruTagOrder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let lowercase_tag = format!("{:?}", self).to_lowercase();
+        write!(f, "{lowercase_tag}")
+    }
+}
+
+/// A tag alias or implication from Danbooru.
+///
+/// Aliases (`/tag_aliases.json`) rewrite the antecedent tag to the
+/// consequent tag. Implications (`/tag_implications.json`) add the
+/// consequent tag to every post tagged with the antecedent.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DanbooruTagRelation {
+    /// The ID of the alias or implication
+    pub id: u32,
+    /// The tag being aliased or implying another tag
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub antecedent_name: String,
+    /// The tag it is aliased to or implies
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub consequent_name: String,
+    /// Status such as `active`, `pending`, `deleted` or `retired`
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub status: String,
+    /// Explanation given when the relation was requested
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub reason: String,
+    /// Forum topic where the relation was discussed
+    #[serde(default, deserialize_with = "lenient::optional_number")]
+    pub forum_topic_id: Option<u32>,
+    /// ID of the user who requested the relation
+    #[serde(default, deserialize_with = "lenient::optional_number")]
+    pub creator_id: Option<u32>,
+    /// ID of the user who approved the relation
+    #[serde(default, deserialize_with = "lenient::optional_number")]
+    pub approver_id: Option<u32>,
+    /// Datestamp of the relation's creation
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub created_at: String,
+    /// Datestamp of the relation's last update
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub updated_at: String,
+    /// Any other fields of the alias or implication.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl DanbooruTagRelation {
+    /// Returns `true` if the relation is currently in effect.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.status == "active"
+    }
+}
+
+/// A tag alias from Danbooru.
+pub type DanbooruTagAlias = DanbooruTagRelation;
+
+/// A tag implication from Danbooru.
+pub type DanbooruTagImplication = DanbooruTagRelation;
+
+/// A tag that frequently appears together with a query, from Danbooru's
+/// `/related_tag.json` endpoint.
+///
+/// The similarity scores compare the set of posts matching the query with
+/// the set of posts carrying [`tag`](Self::tag).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DanbooruRelatedTag {
+    /// The related tag
+    pub tag: DanbooruTag,
+    /// Cosine similarity between the query and the tag, from 0 to 1
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub cosine_similarity: f64,
+    /// Jaccard similarity between the query and the tag, from 0 to 1
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub jaccard_similarity: f64,
+    /// Overlap coefficient between the query and the tag, from 0 to 1
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub overlap_coefficient: f64,
+    /// Fraction of the query's sampled posts that carry the tag
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub frequency: f64,
+    /// Any other fields of the related tag entry.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl DanbooruRelatedTag {
+    /// Returns the name of the related tag's category (e.g. `"character"`).
+    #[must_use]
+    pub fn category_name(&self) -> &'static str {
+        self.tag.category_name()
+    }
+}
+
+impl From<DanbooruRelatedTag> for TagSuggestion {
+    fn from(related: DanbooruRelatedTag) -> Self {
+        TagSuggestion {
+            label: related.tag.name.replace('_', " "),
+            name: related.tag.name,
+            post_count: Some(related.tag.post_count),
+            category: Some(related.tag.category_id),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::model::lenient;
+
+/// An upload on Danbooru.
+///
+/// Uploads are created from files or source URLs and processed in the
+/// background. Once completed, each of its
+/// [`upload_media_assets`](Self::upload_media_assets) can be turned into a
+/// post.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DanbooruUpload {
+    /// The ID of the upload
+    pub id: u32,
+    /// Source URL the files were fetched from, if any
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub source: String,
+    /// Processing status: `pending`, `processing`, `completed` or `error`
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub status: String,
+    /// Error message if processing failed
+    #[serde(default)]
+    pub error: Option<String>,
+    /// Number of files in the upload
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub media_asset_count: u32,
+    /// ID of the user who created the upload
+    #[serde(default, deserialize_with = "lenient::optional_number")]
+    pub uploader_id: Option<u32>,
+    /// The files of the upload
+    #[serde(default)]
+    pub upload_media_assets: Vec<DanbooruUploadMediaAsset>,
+    /// Datestamp of the upload's creation
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub created_at: String,
+    /// Datestamp of the upload's last update
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub updated_at: String,
+    /// Any other fields of the upload, such as `referer_url`.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl DanbooruUpload {
+    /// Returns `true` once processing has stopped, successfully or not.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.is_completed() || self.is_failed()
+    }
+
+    /// Returns `true` if the upload was processed successfully.
+    #[must_use]
+    pub fn is_completed(&self) -> bool {
+        self.status == "completed"
+    }
+
+    /// Returns `true` if processing failed.
+    ///
+    /// See [`error`](Self::error) for the reason.
+    #[must_use]
+    pub fn is_failed(&self) -> bool {
+        self.status == "error" || self.status.starts_with("error:")
+    }
+}
+
+/// A single file of a Danbooru upload.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DanbooruUploadMediaAsset {
+    /// The ID of the upload media asset, used to create a post
+    pub id: u32,
+    /// ID of the upload the file belongs to
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub upload_id: u32,
+    /// ID of the processed media asset
+    #[serde(default, deserialize_with = "lenient::optional_number")]
+    pub media_asset_id: Option<u32>,
+    /// Processing status: `pending`, `processing`, `active` or `failed`
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub status: String,
+    /// URL the file was downloaded from, if any
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub source_url: String,
+    /// Page the file was found on, if any
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub page_url: String,
+    /// Error message if processing failed
+    #[serde(default)]
+    pub error: Option<String>,
+    /// Any other fields of the upload media asset.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::model::lenient;
+
+/// A vote on a Danbooru post.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DanbooruPostVote {
+    /// The ID of the vote
+    pub id: u32,
+    /// ID of the voted post
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub post_id: u32,
+    /// ID of the user who voted
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub user_id: u32,
+    /// `1` for an upvote, `-1` for a downvote
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub score: i32,
+    /// Whether the vote has been removed
+    #[serde(default, deserialize_with = "lenient::boolean")]
+    pub is_deleted: bool,
+    /// Datestamp of the vote
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub created_at: String,
+    /// Datestamp of the vote's last update
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub updated_at: String,
+    /// Any other fields of the vote.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl DanbooruPostVote {
+    /// Returns `true` if this is an upvote.
+    #[must_use]
+    pub fn is_upvote(&self) -> bool {
+        self.score > 0
+    }
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::model::lenient;
+
+/// A wiki page from Danbooru.
+///
+/// Every tag can have a wiki page with the same title describing it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DanbooruWikiPage {
+    /// The ID of the wiki page
+    pub id: u32,
+    /// Page title, usually the name of the tag it describes
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub title: String,
+    /// Page content (DText markup)
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub body: String,
+    /// Alternative names of the subject, e.g. in Japanese
+    #[serde(default)]
+    pub other_names: Vec<String>,
+    /// Whether the page is locked against edits by regular users
+    #[serde(default, deserialize_with = "lenient::boolean")]
+    pub is_locked: bool,
+    /// Whether the page has been deleted
+    #[serde(default, deserialize_with = "lenient::boolean")]
+    pub is_deleted: bool,
+    /// Datestamp of the page's creation
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub created_at: String,
+    /// Datestamp of the page's last update
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub updated_at: String,
+    /// Any other fields of the wiki page.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl DanbooruWikiPage {
+    /// Returns the title with underscores replaced by spaces.
+    #[must_use]
+    pub fn display_title(&self) -> String {
+        self.title.replace('_', " ")
+    }
+}
@@ -4,7 +4,14 @@
 //! responses from the Gelbooru API.
 
 use core::fmt;
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::lenient;
+
+pub use super::comment::Comment;
 
 /// A post from Gelbooru.
 ///
@@ -33,15 +40,35 @@ pub struct GelbooruPost {
     pub source: String,
     /// Post's rating
     pub rating: GelbooruRating,
+    /// Other fields Gelbooru sends, such as `owner`, `status` and `preview_url`.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Wrapper for Gelbooru's API response containing a list of posts.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct GelbooruResponse {
-    #[serde(rename = "post")]
+    /// Paging information, if Gelbooru sent it
+    #[serde(rename = "@attributes", default)]
+    pub attributes: Option<GelbooruAttributes>,
+    #[serde(rename = "post", default)]
     pub posts: Vec<GelbooruPost>,
 }
 
+/// Paging information of a Gelbooru search response.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GelbooruAttributes {
+    /// Maximum number of posts per page
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub limit: u32,
+    /// Number of matching posts before this page
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub offset: u64,
+    /// Total number of posts matching the search
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub count: u64,
+}
+
 /// Post rating classification for Gelbooru.
 ///
 /// See the [Gelbooru ratings wiki](https://gelbooru.com/index.php?page=help&topic=rating)
@@ -68,3 +95,181 @@ impl fmt::Display for GelbooruRating {
         write!(f, "{lovercase_tag}")
     }
 }
+
+/// A tag from Gelbooru's tag API (`page=dapi&s=tag&q=index`).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GelbooruTag {
+    /// The ID of the tag
+    pub id: u32,
+    /// Tag name (with underscores)
+    #[serde(default, deserialize_with = "lenient::string")]
+    pub name: String,
+    /// Number of posts with this tag
+    #[serde(default, deserialize_with = "lenient::number")]
+    pub count: u32,
+    /// Type ID (see [`tag_type`](Self::tag_type))
+    #[serde(rename = "type", default, deserialize_with = "lenient::number")]
+    pub type_id: u8,
+    /// Whether the tag is ambiguous between several meanings
+    #[serde(default, deserialize_with = "lenient::boolean")]
+    pub ambiguous: bool,
+    /// Any other fields of the tag.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl GelbooruTag {
+    /// Returns the tag's type, or `None` if the ID is not a known type.
+    #[must_use]
+    pub fn tag_type(&self) -> Option<GelbooruTagType> {
+        GelbooruTagType::from_id(self.type_id)
+    }
+}
+
+/// Tag type on Gelbooru.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(into = "u8", try_from = "u8")]
+pub enum GelbooruTagType {
+    General,
+    Artist,
+    Copyright,
+    Character,
+    Metadata,
+    Deprecated,
+}
+
+impl GelbooruTagType {
+    /// Returns the numeric ID Gelbooru uses for this type.
+    #[must_use]
+    pub fn id(self) -> u8 {
+        match self {
+            Self::General => 0,
+            Self::Artist => 1,
+            Self::Copyright => 3,
+            Self::Character => 4,
+            Self::Metadata => 5,
+            Self::Deprecated => 6,
+        }
+    }
+
+    /// Returns the type for a numeric ID, if it is known.
+    #[must_use]
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Self::General),
+            1 => Some(Self::Artist),
+            3 => Some(Self::Copyright),
+            4 => Some(Self::Character),
+            5 => Some(Self::Metadata),
+            6 => Some(Self::Deprecated),
+            _ => None,
+        }
+    }
+}
+
+impl From<GelbooruTagType> for u8 {
+    fn from(tag_type: GelbooruTagType) -> u8 {
+        tag_type.id()
+    }
+}
+
+impl TryFrom<u8> for GelbooruTagType {
+    type Error = String;
+
+    fn try_from(id: u8) -> Result<Self, Self::Error> {
+        Self::from_id(id).ok_or_else(|| format!("unknown tag type: {id}"))
+    }
+}
+
+impl fmt::Display for GelbooruTagType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let lowercase_tag = format!("{:?}", self).to_lowercase();
+        write!(f, "{lowercase_tag}")
+    }
+}
+
+/// Sort order for Gelbooru tag searches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GelbooruTagOrder {
+    /// Newest first
+    Date,
+    /// Most posts first
+    Count,
+    /// Alphabetical by name
+    Name,
+}
+
+impl fmt::Display for GelbooruTagOrder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let lowercase_tag = format!("{:?}", self).to_lowercase();
+        write!(f, "{lowercase_tag}")
+    }
+}
+
+/// The tags of a [`GelbooruPost`], grouped by type.
+///
+/// Created by [`GelbooruPost::group_tags`]. Each group keeps the order of
+/// the post's tag string.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GelbooruTagGroups {
+    /// Artist tags
+    pub artist: Vec<String>,
+    /// Character tags
+    pub character: Vec<String>,
+    /// Copyright (series) tags
+    pub copyright: Vec<String>,
+    /// General tags, including tags of unknown type
+    pub general: Vec<String>,
+    /// Metadata tags
+    pub metadata: Vec<String>,
+    /// Deprecated tags
+    pub deprecated: Vec<String>,
+}
+
+impl GelbooruPost {
+    /// Groups the post's tags by type.
+    ///
+    /// Gelbooru posts only carry a flat tag string, so the types have to be
+    /// looked up first, e.g. with [`GelbooruClient::tag_types`]. Tags
+    /// missing from `types` count as general, Gelbooru's default type.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::gelbooru::GelbooruClient;
+    /// use booru_rs::client::Client;
+    ///
+    /// # async fn example() -> booru_rs::error::Result<()> {
+    /// let client = GelbooruClient::builder()
+    ///     .set_credentials("your_api_key", "your_user_id")
+    ///     .build();
+    ///
+    /// let post = client.get_by_id(12345).await?;
+    /// let types = client.tag_types(post.tags.split_whitespace()).await?;
+    /// let groups = post.group_tags(&types);
+    ///
+    /// println!("by {}", groups.artist.join(", "));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`GelbooruClient::tag_types`]: crate::client::gelbooru::GelbooruClient::tag_types
+    #[must_use]
+    pub fn group_tags(&self, types: &HashMap<String, GelbooruTagType>) -> GelbooruTagGroups {
+        let mut groups = GelbooruTagGroups::default();
+
+        for tag in self.tags.split_whitespace() {
+            let group = match types.get(tag) {
+                Some(GelbooruTagType::Artist) => &mut groups.artist,
+                Some(GelbooruTagType::Character) => &mut groups.character,
+                Some(GelbooruTagType::Copyright) => &mut groups.copyright,
+                Some(GelbooruTagType::Metadata) => &mut groups.metadata,
+                Some(GelbooruTagType::Deprecated) => &mut groups.deprecated,
+                Some(GelbooruTagType::General) | None => &mut groups.general,
+            };
+            group.push(tag.to_string());
+        }
+
+        groups
+    }
+}
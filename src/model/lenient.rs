@@ -0,0 +1,608 @@
+//! Lenient decoding of post pages.
+//!
+//! Booru APIs change their response schemas without notice: a field turns
+//! `null`, a number arrives as a string, or a new rating value shows up. In
+//! strict mode a single malformed post fails the whole page. The types in
+//! this module let a page be decoded post by post instead, so that malformed
+//! posts are reported individually while the rest of the page survives.
+//!
+//! A post that fails to decode is retried with lenient coercions: numbers
+//! sent as strings, booleans sent as `0`/`1` or `"true"`, and `null` in
+//! non-optional fields. Only posts that still fail, e.g. because a required
+//! field is missing, are reported. The post models themselves stay strict.
+//!
+//! See [`Client::get_lenient`](crate::client::Client::get_lenient) and
+//! [`ClientBuilder::lenient`](crate::client::ClientBuilder::lenient).
+
+use core::fmt;
+#[cfg(any(feature = "danbooru", feature = "gelbooru", feature = "rule34"))]
+use core::str::FromStr;
+
+#[cfg(any(
+    feature = "danbooru",
+    feature = "gelbooru",
+    feature = "rule34",
+    feature = "safebooru"
+))]
+use serde::de::{DeserializeOwned, DeserializeSeed, Error as _, MapAccess, SeqAccess, Visitor};
+#[cfg(any(feature = "danbooru", feature = "gelbooru", feature = "rule34"))]
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+/// A page of posts decoded in lenient mode.
+///
+/// Contains every post that could be decoded, plus one [`ItemError`] for
+/// each post that could not.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LenientPosts<P> {
+    /// Posts that were decoded successfully, in response order.
+    pub posts: Vec<P>,
+    /// Posts that failed to decode.
+    pub errors: Vec<ItemError>,
+}
+
+impl<P> LenientPosts<P> {
+    /// Returns `true` if every post on the page was decoded.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+impl<P> Default for LenientPosts<P> {
+    fn default() -> Self {
+        Self {
+            posts: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+}
+
+impl<P> From<Vec<P>> for LenientPosts<P> {
+    fn from(posts: Vec<P>) -> Self {
+        Self {
+            posts,
+            errors: Vec::new(),
+        }
+    }
+}
+
+/// A post that could not be decoded in lenient mode.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemError {
+    /// Position of the post in the API response.
+    pub index: usize,
+    /// The post's ID, if it could be read from the raw item.
+    pub id: Option<u64>,
+    /// Description of the decoding failure.
+    pub message: String,
+    /// The raw item as returned by the API, so no data is lost.
+    pub raw: Value,
+}
+
+impl fmt::Display for ItemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.id {
+            Some(id) => write!(f, "post #{id} (item {}): {}", self.index, self.message),
+            None => write!(f, "item {}: {}", self.index, self.message),
+        }
+    }
+}
+
+impl std::error::Error for ItemError {}
+
+/// Decodes each raw item separately, collecting failures instead of
+/// aborting on the first one.
+///
+/// Items that fail to decode are retried with the coercions of
+/// [`Lenient`]; the error of the retry is reported if that fails too.
+#[cfg(any(
+    feature = "danbooru",
+    feature = "gelbooru",
+    feature = "rule34",
+    feature = "safebooru"
+))]
+pub(crate) fn decode_items<P: DeserializeOwned>(items: Vec<Value>) -> LenientPosts<P> {
+    let mut page = LenientPosts {
+        posts: Vec::with_capacity(items.len()),
+        errors: Vec::new(),
+    };
+
+    for (index, raw) in items.into_iter().enumerate() {
+        let decoded = P::deserialize(&raw).or_else(|_| P::deserialize(Lenient(raw.clone())));
+        match decoded {
+            Ok(post) => page.posts.push(post),
+            Err(error) => page.errors.push(ItemError {
+                index,
+                id: raw.get("id").and_then(value_as_u64),
+                message: error.to_string(),
+                raw,
+            }),
+        }
+    }
+
+    page
+}
+
+#[cfg(any(
+    feature = "danbooru",
+    feature = "gelbooru",
+    feature = "rule34",
+    feature = "safebooru"
+))]
+fn value_as_u64(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// A JSON value decoded with the coercions of lenient mode.
+///
+/// Scalars are converted to the type the model asks for: numeric strings to
+/// numbers, numbers and booleans to strings, `0`/`1` and `"true"`/`"false"`
+/// to booleans, and `null` to zero, `false` or an empty string. `null` and
+/// `""` in optional fields become `None`. Everything else decodes exactly
+/// like a [`Value`]; in particular, missing fields are still errors.
+#[cfg(any(
+    feature = "danbooru",
+    feature = "gelbooru",
+    feature = "rule34",
+    feature = "safebooru"
+))]
+struct Lenient(Value);
+
+#[cfg(any(
+    feature = "danbooru",
+    feature = "gelbooru",
+    feature = "rule34",
+    feature = "safebooru"
+))]
+macro_rules! lenient_number {
+    ($($method:ident => $visit:ident($ty:ty),)*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+            match self.0 {
+                Value::Null => visitor.$visit(<$ty>::default()),
+                Value::String(s) if s.trim().is_empty() => visitor.$visit(<$ty>::default()),
+                Value::String(s) => match s.trim().parse::<$ty>() {
+                    Ok(n) => visitor.$visit(n),
+                    Err(_) => Err(serde_json::Error::custom(format!("invalid number: {s:?}"))),
+                },
+                other => other.$method(visitor),
+            }
+        }
+    )*};
+}
+
+#[cfg(any(
+    feature = "danbooru",
+    feature = "gelbooru",
+    feature = "rule34",
+    feature = "safebooru"
+))]
+macro_rules! forward_to_value {
+    ($($method:ident,)*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+            self.0.$method(visitor)
+        }
+    )*};
+}
+
+#[cfg(any(
+    feature = "danbooru",
+    feature = "gelbooru",
+    feature = "rule34",
+    feature = "safebooru"
+))]
+impl<'de> serde::Deserializer<'de> for Lenient {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        match self.0 {
+            Value::Array(items) => visitor.visit_seq(LenientSeq(items.into_iter())),
+            Value::Object(map) => visitor.visit_map(LenientMap {
+                entries: map.into_iter(),
+                value: None,
+            }),
+            other => other.deserialize_any(visitor),
+        }
+    }
+
+    lenient_number! {
+        deserialize_i8 => visit_i8(i8),
+        deserialize_i16 => visit_i16(i16),
+        deserialize_i32 => visit_i32(i32),
+        deserialize_i64 => visit_i64(i64),
+        deserialize_u8 => visit_u8(u8),
+        deserialize_u16 => visit_u16(u16),
+        deserialize_u32 => visit_u32(u32),
+        deserialize_u64 => visit_u64(u64),
+        deserialize_f32 => visit_f32(f32),
+        deserialize_f64 => visit_f64(f64),
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        match self.0 {
+            Value::Null => visitor.visit_bool(false),
+            Value::Number(n) => visitor.visit_bool(n.as_f64().is_some_and(|n| n != 0.0)),
+            Value::String(s) => match s.trim() {
+                "" | "0" | "false" => visitor.visit_bool(false),
+                "1" | "true" => visitor.visit_bool(true),
+                other => Err(serde_json::Error::custom(format!(
+                    "invalid boolean: {other:?}"
+                ))),
+            },
+            other => other.deserialize_bool(visitor),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        match self.0 {
+            Value::Null => visitor.visit_string(String::new()),
+            Value::Number(n) => visitor.visit_string(n.to_string()),
+            Value::Bool(b) => visitor.visit_string(b.to_string()),
+            other => other.deserialize_string(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        match self.0 {
+            Value::Null => visitor.visit_none(),
+            Value::String(s) if s.trim().is_empty() => visitor.visit_none(),
+            other => visitor.visit_some(Lenient(other)),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> serde_json::Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        match self.0 {
+            Value::Array(items) => visitor.visit_seq(LenientSeq(items.into_iter())),
+            other => other.deserialize_seq(visitor),
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> serde_json::Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> serde_json::Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        match self.0 {
+            Value::Object(map) => visitor.visit_map(LenientMap {
+                entries: map.into_iter(),
+                value: None,
+            }),
+            other => other.deserialize_map(visitor),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> serde_json::Result<V::Value> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> serde_json::Result<V::Value> {
+        self.0.deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> serde_json::Result<V::Value> {
+        self.0.deserialize_unit_struct(name, visitor)
+    }
+
+    forward_to_value! {
+        deserialize_char,
+        deserialize_bytes,
+        deserialize_byte_buf,
+        deserialize_unit,
+        deserialize_identifier,
+        deserialize_ignored_any,
+    }
+}
+
+/// The elements of an array, each decoded with [`Lenient`].
+#[cfg(any(
+    feature = "danbooru",
+    feature = "gelbooru",
+    feature = "rule34",
+    feature = "safebooru"
+))]
+struct LenientSeq(std::vec::IntoIter<Value>);
+
+#[cfg(any(
+    feature = "danbooru",
+    feature = "gelbooru",
+    feature = "rule34",
+    feature = "safebooru"
+))]
+impl<'de> SeqAccess<'de> for LenientSeq {
+    type Error = serde_json::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> serde_json::Result<Option<T::Value>> {
+        self.0
+            .next()
+            .map(|value| seed.deserialize(Lenient(value)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+/// The entries of an object, with each value decoded with [`Lenient`].
+#[cfg(any(
+    feature = "danbooru",
+    feature = "gelbooru",
+    feature = "rule34",
+    feature = "safebooru"
+))]
+struct LenientMap {
+    entries: serde_json::map::IntoIter,
+    value: Option<Value>,
+}
+
+#[cfg(any(
+    feature = "danbooru",
+    feature = "gelbooru",
+    feature = "rule34",
+    feature = "safebooru"
+))]
+impl<'de> MapAccess<'de> for LenientMap {
+    type Error = serde_json::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> serde_json::Result<Option<K::Value>> {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(Value::String(key)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> serde_json::Result<V::Value> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| serde_json::Error::custom("value requested before key"))?;
+        seed.deserialize(Lenient(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+/// Deserializes a number that may also arrive as a numeric string or `null`.
+///
+/// `null` and empty strings become the type's default value.
+#[cfg(any(feature = "danbooru", feature = "gelbooru", feature = "rule34"))]
+pub(crate) fn number<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr + Default + DeserializeOwned,
+{
+    Ok(optional_number(deserializer)?.unwrap_or_default())
+}
+
+/// Deserializes an optional number that may also arrive as a numeric string.
+///
+/// `null` and empty strings become `None`.
+#[cfg(any(feature = "danbooru", feature = "gelbooru", feature = "rule34"))]
+pub(crate) fn optional_number<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr + DeserializeOwned,
+{
+    match Option::<Value>::deserialize(deserializer)? {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(s)) if s.trim().is_empty() => Ok(None),
+        Some(Value::String(s)) => s
+            .trim()
+            .parse()
+            .map(Some)
+            .map_err(|_| D::Error::custom(format!("invalid number: {s:?}"))),
+        Some(value @ Value::Number(_)) => T::deserialize(value).map(Some).map_err(D::Error::custom),
+        Some(other) => Err(D::Error::custom(format!(
+            "invalid type: expected a number, found {other}"
+        ))),
+    }
+}
+
+/// Deserializes a string that may also arrive as `null` or a number.
+///
+/// `null` becomes an empty string.
+#[cfg(any(feature = "danbooru", feature = "gelbooru", feature = "rule34"))]
+pub(crate) fn string<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<Value>::deserialize(deserializer)? {
+        None | Some(Value::Null) => Ok(String::new()),
+        Some(Value::String(s)) => Ok(s),
+        Some(value @ (Value::Number(_) | Value::Bool(_))) => Ok(value.to_string()),
+        Some(other) => Err(D::Error::custom(format!(
+            "invalid type: expected a string, found {other}"
+        ))),
+    }
+}
+
+/// Deserializes a boolean that may also arrive as `0`/`1`, a string, or `null`.
+///
+/// `null` becomes `false`.
+#[cfg(any(feature = "danbooru", feature = "gelbooru"))]
+pub(crate) fn boolean<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<Value>::deserialize(deserializer)? {
+        None | Some(Value::Null) => Ok(false),
+        Some(Value::Bool(b)) => Ok(b),
+        Some(Value::Number(n)) => Ok(n.as_f64().is_some_and(|n| n != 0.0)),
+        Some(Value::String(s)) => match s.trim() {
+            "" | "0" | "false" => Ok(false),
+            "1" | "true" => Ok(true),
+            other => Err(D::Error::custom(format!("invalid boolean: {other:?}"))),
+        },
+        Some(other) => Err(D::Error::custom(format!(
+            "invalid type: expected a boolean, found {other}"
+        ))),
+    }
+}
+
+#[cfg(all(test, any(feature = "danbooru", feature = "gelbooru")))]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Item {
+        id: u32,
+        #[serde(default, deserialize_with = "number")]
+        score: i32,
+        #[serde(default, deserialize_with = "optional_number")]
+        parent_id: Option<u32>,
+        #[serde(default, deserialize_with = "string")]
+        source: String,
+        #[serde(default, deserialize_with = "boolean")]
+        has_notes: bool,
+    }
+
+    #[test]
+    fn test_coerces_drifted_scalars() {
+        let item: Item = serde_json::from_value(json!({
+            "id": 1,
+            "score": "42",
+            "parent_id": "",
+            "source": null,
+            "has_notes": "true",
+        }))
+        .unwrap();
+
+        assert_eq!(
+            item,
+            Item {
+                id: 1,
+                score: 42,
+                parent_id: None,
+                source: String::new(),
+                has_notes: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let item: Item = serde_json::from_value(json!({ "id": 7 })).unwrap();
+
+        assert_eq!(item.score, 0);
+        assert_eq!(item.parent_id, None);
+        assert!(!item.has_notes);
+    }
+
+    #[test]
+    fn test_rejects_garbage_numbers() {
+        let result: serde_json::Result<Item> =
+            serde_json::from_value(json!({ "id": 1, "score": "lots" }));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_decode_items_reports_failures() {
+        let page: LenientPosts<Item> = decode_items(vec![
+            json!({ "id": 1 }),
+            json!({ "id": "2", "score": [] }),
+            json!({ "id": 3 }),
+        ]);
+
+        assert_eq!(page.posts.len(), 2);
+        assert!(!page.is_complete());
+        assert_eq!(page.errors.len(), 1);
+        assert_eq!(page.errors[0].index, 1);
+        assert_eq!(page.errors[0].id, Some(2));
+        assert_eq!(page.errors[0].raw["score"], json!([]));
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct StrictItem {
+        id: u32,
+        score: i32,
+        parent_id: Option<u32>,
+        tags: Vec<String>,
+        has_notes: bool,
+    }
+
+    #[test]
+    fn test_decode_items_coerces_strict_models() {
+        let drifted = json!({
+            "id": "1",
+            "score": "12",
+            "parent_id": "",
+            "tags": ["a", 2],
+            "has_notes": 0,
+        });
+        assert!(serde_json::from_value::<StrictItem>(drifted.clone()).is_err());
+
+        let page: LenientPosts<StrictItem> = decode_items(vec![
+            drifted,
+            json!({ "id": 2, "score": 1, "parent_id": null, "tags": [] }),
+        ]);
+
+        assert_eq!(page.posts.len(), 1);
+        let item = &page.posts[0];
+        assert_eq!((item.id, item.score, item.parent_id), (1, 12, None));
+        assert_eq!(item.tags, ["a", "2"]);
+        assert!(!item.has_notes);
+
+        assert_eq!(page.errors.len(), 1);
+        assert_eq!(page.errors[0].id, Some(2));
+        assert!(page.errors[0].message.contains("has_notes"));
+    }
+}
@@ -3,14 +3,19 @@
 //! This module contains the post and rating types for each supported booru site,
 //! as well as a common [`Post`] trait that provides a unified interface.
 
+#[cfg(any(feature = "gelbooru", feature = "rule34"))]
+pub mod comment;
 #[cfg(feature = "danbooru")]
 pub mod danbooru;
 #[cfg(feature = "gelbooru")]
 pub mod gelbooru;
+pub mod lenient;
 #[cfg(feature = "rule34")]
 pub mod rule34;
 #[cfg(feature = "safebooru")]
 pub mod safebooru;
+#[cfg(any(feature = "gelbooru", feature = "rule34", feature = "safebooru"))]
+pub(crate) mod xml;
 
 /// Common interface for post types across different booru sites.
 ///
@@ -54,6 +59,79 @@ pub trait Post {
 
     /// Returns the source URL for the image, if available.
     fn source(&self) -> Option<&str>;
+
+    /// Returns the kind of media the post's file is.
+    ///
+    /// By default this is detected from the extension of the file URL.
+    fn media_type(&self) -> MediaType {
+        self.file_url()
+            .and_then(url_extension)
+            .map_or(MediaType::Other, MediaType::from_extension)
+    }
+
+    /// Returns the URL of the smallest file that still fills a
+    /// `width`x`height` box, falling back to the largest file available.
+    ///
+    /// Sites that only provide the original file return
+    /// [`file_url`](Self::file_url).
+    fn file_url_for_size(&self, width: u32, height: u32) -> Option<&str> {
+        let _ = (width, height);
+        self.file_url()
+    }
+}
+
+/// Kind of media a post's file is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MediaType {
+    /// A still image (JPEG, PNG, WebP, AVIF, ...)
+    Image,
+    /// A GIF, which may be animated
+    Gif,
+    /// A video (MP4 or WebM)
+    Video,
+    /// A Pixiv ugoira: a ZIP archive of animation frames
+    Ugoira,
+    /// A file type that isn't recognised
+    Other,
+}
+
+impl MediaType {
+    /// Returns the media type for a file extension (without the dot).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use booru_rs::model::MediaType;
+    ///
+    /// assert_eq!(MediaType::from_extension("webm"), MediaType::Video);
+    /// assert_eq!(MediaType::from_extension("ZIP"), MediaType::Ugoira);
+    /// assert_eq!(MediaType::from_extension("swf"), MediaType::Other);
+    /// ```
+    #[must_use]
+    pub fn from_extension(ext: &str) -> Self {
+        match ext.to_ascii_lowercase().as_str() {
+            "jpg" | "jpeg" | "png" | "webp" | "avif" | "bmp" => Self::Image,
+            "gif" => Self::Gif,
+            "mp4" | "webm" | "m4v" | "mkv" => Self::Video,
+            "zip" => Self::Ugoira,
+            _ => Self::Other,
+        }
+    }
+
+    /// Returns `true` for media that can move: GIFs, videos and ugoira.
+    #[must_use]
+    pub fn is_animated(self) -> bool {
+        matches!(self, Self::Gif | Self::Video | Self::Ugoira)
+    }
+}
+
+/// Returns the file extension of a URL, ignoring any query string or fragment.
+pub(crate) fn url_extension(url: &str) -> Option<&str> {
+    let path = url.split(['?', '#']).next()?;
+    let file = path.rsplit('/').next()?;
+    let (_, ext) = file.rsplit_once('.')?;
+
+    (!ext.is_empty()).then_some(ext)
 }
 
 // Implement Post trait for all post types
@@ -94,6 +172,28 @@ impl Post for danbooru::DanbooruPost {
             Some(&self.source)
         }
     }
+
+    fn media_type(&self) -> MediaType {
+        match self
+            .media_asset
+            .as_ref()
+            .map_or(&self.file_ext, |asset| &asset.file_ext)
+        {
+            ext if ext.is_empty() => self
+                .file_url()
+                .and_then(url_extension)
+                .map_or(MediaType::Other, MediaType::from_extension),
+            ext => MediaType::from_extension(ext),
+        }
+    }
+
+    fn file_url_for_size(&self, width: u32, height: u32) -> Option<&str> {
+        self.media_asset
+            .as_ref()
+            .and_then(|asset| asset.best_variant(width, height))
+            .map(|variant| variant.url.as_str())
+            .or_else(|| self.file_url())
+    }
 }
 
 #[cfg(feature = "gelbooru")]
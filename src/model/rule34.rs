@@ -5,6 +5,9 @@
 
 use core::fmt;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+pub use super::comment::Comment;
 
 /// A post from Rule34.
 ///
@@ -60,6 +63,9 @@ pub struct Rule34Post {
     /// Image hash
     #[serde(default)]
     pub hash: String,
+    /// Other fields Rule34 sends, such as `sample` and `sample_width`.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Post rating classification for Rule34.
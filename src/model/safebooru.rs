@@ -5,6 +5,7 @@
 
 use core::fmt;
 use serde::Deserialize;
+use serde_json::{Map, Value};
 
 /// A post from Safebooru.
 ///
@@ -36,6 +37,9 @@ pub struct SafebooruPost {
     /// field.
     pub change: u32,
     pub rating: SafebooruRating,
+    /// Other fields Safebooru sends, such as `owner` and `parent_id`.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Post rating classification for Safebooru.
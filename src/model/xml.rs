@@ -0,0 +1,127 @@
+//! Decoding of the XML lists served by Gelbooru-based sites.
+//!
+//! Some DAPI data is only available as XML, such as comments and the total
+//! number of posts matching a search. These documents carry all data in
+//! attributes (`<posts count="2"><post id="1" .../></posts>`), so each
+//! element is converted to a JSON object of strings and decoded with the
+//! same lenient models as the JSON API.
+
+use std::borrow::Cow;
+
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+use serde::de::Error as _;
+use serde_json::{Map, Value};
+
+/// A list element and the items inside it.
+#[derive(Debug)]
+pub(crate) struct XmlList {
+    /// Attributes of the list element
+    pub attributes: Map<String, Value>,
+    /// Attributes of each item element, in document order
+    pub items: Vec<Value>,
+}
+
+impl XmlList {
+    /// Returns a numeric attribute of the list element.
+    pub fn number(&self, name: &str) -> Option<u64> {
+        self.attributes.get(name)?.as_str()?.trim().parse().ok()
+    }
+}
+
+/// Decodes a list such as `<comments><comment id="1"/></comments>`, given
+/// the names of the list and item elements.
+///
+/// Returns `None` if the document is not such a list, e.g. an error
+/// response. Malformed XML inside the list is reported as a JSON error, like
+/// any other response that cannot be decoded.
+pub(crate) fn parse_list(xml: &str, list: &str, item: &str) -> Option<serde_json::Result<XmlList>> {
+    let mut reader = Reader::from_str(xml);
+    let mut result: Option<XmlList> = None;
+
+    loop {
+        let element = match reader.read_event() {
+            Ok(Event::Start(element) | Event::Empty(element)) => element,
+            Ok(Event::End(end)) if result.is_some() && end.name().as_ref() == list.as_bytes() => {
+                break;
+            }
+            Ok(Event::Eof) => break,
+            Ok(_) => continue,
+            Err(error) if result.is_some() => {
+                return Some(Err(serde_json::Error::custom(error)));
+            }
+            Err(_) => return None,
+        };
+
+        let name = element.name();
+        match &mut result {
+            None if name.as_ref() == list.as_bytes() => {
+                result = Some(XmlList {
+                    attributes: attributes(&element),
+                    items: Vec::new(),
+                });
+            }
+            Some(found) if name.as_ref() == item.as_bytes() => {
+                found.items.push(Value::Object(attributes(&element)));
+            }
+            _ => {}
+        }
+    }
+
+    result.map(Ok)
+}
+
+/// Collects an element's attributes as JSON strings.
+///
+/// Values that cannot be unescaped (such as HTML entities XML doesn't
+/// know) are kept as written.
+fn attributes(element: &BytesStart) -> Map<String, Value> {
+    element
+        .attributes()
+        .flatten()
+        .map(|attribute| {
+            let key = String::from_utf8_lossy(attribute.key.as_ref()).into_owned();
+            let value = attribute.unescape_value().unwrap_or_else(|_| {
+                String::from_utf8_lossy(&attribute.value)
+                    .into_owned()
+                    .into()
+            });
+
+            (key, Value::String(Cow::into_owned(value)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_post_list() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <posts count="1234" offset="200">
+                <post id="1" rating="s" tags=" cat_ears "/>
+                <post id="2" rating="e" tags="landscape"/>
+            </posts>"#;
+
+        let list = parse_list(xml, "posts", "post").unwrap().unwrap();
+
+        assert_eq!(list.number("count"), Some(1234));
+        assert_eq!(list.number("offset"), Some(200));
+        assert_eq!(list.number("limit"), None);
+        assert_eq!(list.items.len(), 2);
+        assert_eq!(list.items[0]["id"], "1");
+        assert_eq!(list.items[1]["tags"], "landscape");
+    }
+
+    #[test]
+    fn test_parse_list_ignores_other_elements() {
+        let xml = r#"<posts count="0"></posts><post id="1"/>"#;
+
+        let list = parse_list(xml, "posts", "post").unwrap().unwrap();
+
+        assert_eq!(list.number("count"), Some(0));
+        assert!(list.items.is_empty());
+        assert!(parse_list(xml, "comments", "comment").is_none());
+    }
+}
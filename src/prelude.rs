@@ -42,6 +42,9 @@ pub use crate::retry::RetryConfig;
 // Rate limiting
 pub use crate::ratelimit::RateLimiter;
 
+// Circuit breaking
+pub use crate::circuit::CircuitBreaker;
+
 // Caching
 pub use crate::cache::{Cache, CacheConfig};
 
@@ -18,11 +18,143 @@
 //! // ... make request ...
 //! # }
 //! ```
+//!
+//! # Adaptive Mode
+//!
+//! A limiter created with [`RateLimiter::adaptive`] adjusts its rate based on
+//! how the server responds. When a site answers with HTTP 429 or 503, the
+//! limiter honours `Retry-After`, cuts its refill rate multiplicatively and
+//! then slowly recovers towards the configured rate. Rate limit headers such
+//! as `X-RateLimit-Remaining` are used to keep the local token count in sync
+//! with the server's view.
+//!
+//! ```no_run
+//! use booru_rs::prelude::*;
+//! use booru_rs::ratelimit::RateLimiter;
+//! use std::time::Duration;
+//!
+//! # async fn example() -> Result<()> {
+//! let limiter = RateLimiter::adaptive(10, Duration::from_secs(1));
+//!
+//! let client = SafebooruClient::builder()
+//!     .rate_limiter(limiter.clone())
+//!     .tag("landscape")?
+//!     .build();
+//!
+//! let posts = client.get().await?;
+//!
+//! // Inspect how hard the site is currently pushing back
+//! let status = limiter.status().await;
+//! println!("{:.1} requests/s (throttled: {})", status.requests_per_second(), status.throttled);
+//! # Ok(())
+//! # }
+//! ```
 
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
+
+use reqwest::StatusCode;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
 use tokio::sync::Mutex;
 
+/// Header names used by various sites to report the remaining request quota.
+const REMAINING_HEADERS: &[&str] = &[
+    "x-ratelimit-remaining",
+    "x-rate-limit-remaining",
+    "ratelimit-remaining",
+];
+
+/// Header names used by various sites to report when the quota resets.
+const RESET_HEADERS: &[&str] = &["x-ratelimit-reset", "x-rate-limit-reset", "ratelimit-reset"];
+
+/// Longest delay accepted from a server; longer values are clamped to it.
+const MAX_SERVER_DELAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Lowest `min_factor` accepted by [`RateLimiter::with_adaptive_config`], so
+/// the rate never reaches zero.
+const MIN_ADAPTIVE_FACTOR: f64 = 0.001;
+
+/// Configuration for the adaptive mode of a [`RateLimiter`].
+///
+/// The limiter follows an additive-increase/multiplicative-decrease scheme:
+/// every throttling response multiplies the current rate by
+/// `decrease_factor`, and every `recovery_interval` without throttling adds
+/// `recovery_step` (as a fraction of the configured rate) back.
+///
+/// Out-of-range values are clamped by [`RateLimiter::with_adaptive_config`]:
+/// `decrease_factor` and `min_factor` to `0.001..=1`, `recovery_step` to at
+/// least zero. Non-finite values are replaced by the defaults.
+#[derive(Debug, Clone, Copy)]
+pub struct AdaptiveConfig {
+    /// Multiplier applied to the rate on HTTP 429/503 (e.g. `0.5` halves it).
+    pub decrease_factor: f64,
+    /// Fraction of the configured rate restored per recovery interval.
+    pub recovery_step: f64,
+    /// How long the limiter must go without throttling before recovering.
+    pub recovery_interval: Duration,
+    /// Lowest fraction of the configured rate the limiter will drop to.
+    pub min_factor: f64,
+}
+
+impl AdaptiveConfig {
+    /// Returns the configuration with every factor in its valid range.
+    fn clamped(self) -> Self {
+        let default = Self::default();
+        let finite_or =
+            |value: f64, fallback: f64| if value.is_finite() { value } else { fallback };
+
+        Self {
+            decrease_factor: finite_or(self.decrease_factor, default.decrease_factor)
+                .clamp(MIN_ADAPTIVE_FACTOR, 1.0),
+            recovery_step: finite_or(self.recovery_step, default.recovery_step).max(0.0),
+            recovery_interval: self.recovery_interval,
+            min_factor: finite_or(self.min_factor, default.min_factor)
+                .clamp(MIN_ADAPTIVE_FACTOR, 1.0),
+        }
+    }
+}
+
+impl Default for AdaptiveConfig {
+    /// Default configuration: halve on throttling, recover 10% every 10 seconds.
+    fn default() -> Self {
+        Self {
+            decrease_factor: 0.5,
+            recovery_step: 0.1,
+            recovery_interval: Duration::from_secs(10),
+            min_factor: 0.05,
+        }
+    }
+}
+
+/// A snapshot of a [`RateLimiter`]'s current limit.
+///
+/// Returned by [`RateLimiter::status`]. Useful for dashboards and logging
+/// to see when a site is pushing back.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitStatus {
+    /// The configured number of requests per interval.
+    pub configured_requests: u32,
+    /// The number of requests per interval currently allowed.
+    pub current_requests: f64,
+    /// The interval the request counts refer to.
+    pub per_interval: Duration,
+    /// Number of tokens currently available.
+    pub available: u32,
+    /// Whether the limiter is currently running below its configured rate.
+    pub throttled: bool,
+    /// Time remaining until requests are allowed again, if the server asked
+    /// us to back off.
+    pub paused_for: Option<Duration>,
+}
+
+impl RateLimitStatus {
+    /// Returns the currently allowed rate in requests per second.
+    #[must_use]
+    pub fn requests_per_second(&self) -> f64 {
+        self.current_requests / self.per_interval.as_secs_f64()
+    }
+}
+
 /// A token bucket rate limiter for controlling API request rates.
 ///
 /// This limiter uses a token bucket algorithm where tokens are replenished
@@ -61,6 +193,8 @@ struct RateLimiterConfig {
     capacity: u32,
     /// How long it takes to refill the entire bucket.
     refill_interval: Duration,
+    /// Adaptive behaviour, if enabled.
+    adaptive: Option<AdaptiveConfig>,
 }
 
 struct RateLimiterState {
@@ -68,6 +202,12 @@ struct RateLimiterState {
     tokens: f64,
     /// When we last updated the token count.
     last_update: Instant,
+    /// Fraction of the configured rate currently allowed (adaptive mode only).
+    rate_factor: f64,
+    /// When the rate was last decreased or recovered.
+    last_adjustment: Instant,
+    /// No requests are allowed before this instant.
+    paused_until: Option<Instant>,
 }
 
 impl RateLimiter {
@@ -92,18 +232,62 @@ impl RateLimiter {
     /// ```
     #[must_use]
     pub fn new(requests: u32, per_interval: Duration) -> Self {
+        let now = Instant::now();
         Self {
             state: Arc::new(Mutex::new(RateLimiterState {
                 tokens: requests as f64,
-                last_update: Instant::now(),
+                last_update: now,
+                rate_factor: 1.0,
+                last_adjustment: now,
+                paused_until: None,
             })),
             config: RateLimiterConfig {
                 capacity: requests,
                 refill_interval: per_interval,
+                adaptive: None,
             },
         }
     }
 
+    /// Creates an adaptive rate limiter.
+    ///
+    /// The limiter starts at `requests` per `per_interval` and adjusts itself
+    /// based on the responses passed to [`observe`](Self::observe). Clients
+    /// configured with [`ClientBuilder::rate_limiter`] report every response
+    /// automatically.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use booru_rs::ratelimit::RateLimiter;
+    /// use std::time::Duration;
+    ///
+    /// let limiter = RateLimiter::adaptive(10, Duration::from_secs(1));
+    /// assert!(limiter.is_adaptive());
+    /// ```
+    ///
+    /// [`ClientBuilder::rate_limiter`]: crate::client::ClientBuilder::rate_limiter
+    #[must_use]
+    pub fn adaptive(requests: u32, per_interval: Duration) -> Self {
+        Self::new(requests, per_interval).with_adaptive_config(AdaptiveConfig::default())
+    }
+
+    /// Enables adaptive mode with a custom configuration.
+    ///
+    /// Factors outside their valid range are clamped (see [`AdaptiveConfig`]),
+    /// so throttling can never stop the limiter entirely.
+    #[must_use]
+    pub fn with_adaptive_config(mut self, config: AdaptiveConfig) -> Self {
+        self.config.adaptive = Some(config.clamped());
+        self
+    }
+
+    /// Returns `true` if this limiter adapts its rate to server responses.
+    #[must_use]
+    pub fn is_adaptive(&self) -> bool {
+        self.config.adaptive.is_some()
+    }
+
     /// Creates a rate limiter suitable for most booru APIs.
     ///
     /// This uses conservative defaults (2 requests/second) that should
@@ -140,16 +324,16 @@ impl RateLimiter {
                 let mut state = self.state.lock().await;
                 self.refill_tokens(&mut state);
 
-                if state.tokens >= 1.0 {
+                if let Some(remaining) = Self::pause_remaining(&state) {
+                    remaining
+                } else if state.tokens >= 1.0 {
                     state.tokens -= 1.0;
                     return;
+                } else {
+                    // Calculate how long until we have 1 token
+                    let tokens_needed = 1.0 - state.tokens;
+                    Duration::from_secs_f64(tokens_needed / self.refill_rate(&state))
                 }
-
-                // Calculate how long until we have 1 token
-                let tokens_needed = 1.0 - state.tokens;
-                let refill_rate =
-                    self.config.capacity as f64 / self.config.refill_interval.as_secs_f64();
-                Duration::from_secs_f64(tokens_needed / refill_rate)
             };
 
             tokio::time::sleep(wait_time).await;
@@ -180,6 +364,10 @@ impl RateLimiter {
         let mut state = self.state.lock().await;
         self.refill_tokens(&mut state);
 
+        if Self::pause_remaining(&state).is_some() {
+            return false;
+        }
+
         if state.tokens >= 1.0 {
             state.tokens -= 1.0;
             true
@@ -195,18 +383,216 @@ impl RateLimiter {
         state.tokens as u32
     }
 
+    /// Returns the number of requests per interval currently allowed.
+    ///
+    /// For non-adaptive limiters this is always the configured rate.
+    pub async fn current_limit(&self) -> f64 {
+        let mut state = self.state.lock().await;
+        self.refill_tokens(&mut state);
+        self.config.capacity as f64 * state.rate_factor
+    }
+
+    /// Returns a snapshot of the limiter's current state.
+    pub async fn status(&self) -> RateLimitStatus {
+        let mut state = self.state.lock().await;
+        self.refill_tokens(&mut state);
+        RateLimitStatus {
+            configured_requests: self.config.capacity,
+            current_requests: self.config.capacity as f64 * state.rate_factor,
+            per_interval: self.config.refill_interval,
+            available: state.tokens.max(0.0) as u32,
+            throttled: state.rate_factor < 1.0,
+            paused_for: Self::pause_remaining(&state),
+        }
+    }
+
+    /// Feeds a server response into the limiter.
+    ///
+    /// In adaptive mode, HTTP 429 and 503 responses reduce the rate and pause
+    /// the limiter for the duration given by `Retry-After`, while rate limit
+    /// headers on other responses resynchronize the available token count.
+    /// Non-adaptive limiters ignore this call.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use booru_rs::ratelimit::RateLimiter;
+    /// use reqwest::StatusCode;
+    /// use reqwest::header::HeaderMap;
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() {
+    /// let limiter = RateLimiter::adaptive(10, Duration::from_secs(1));
+    ///
+    /// limiter.observe(StatusCode::TOO_MANY_REQUESTS, &HeaderMap::new()).await;
+    /// assert_eq!(limiter.current_limit().await, 5.0);
+    /// # }
+    /// ```
+    pub async fn observe(&self, status: StatusCode, headers: &HeaderMap) {
+        let Some(adaptive) = self.config.adaptive else {
+            return;
+        };
+
+        let mut state = self.state.lock().await;
+        self.refill_tokens(&mut state);
+        let now = Instant::now();
+
+        if status == StatusCode::TOO_MANY_REQUESTS || status == StatusCode::SERVICE_UNAVAILABLE {
+            state.rate_factor =
+                (state.rate_factor * adaptive.decrease_factor).max(adaptive.min_factor);
+            state.last_adjustment = now;
+            state.tokens = state.tokens.min(0.0);
+
+            if let Some(delay) = parse_retry_after(headers) {
+                Self::pause(&mut state, now + delay);
+            }
+            return;
+        }
+
+        if let Some(remaining) = header_f64(headers, REMAINING_HEADERS) {
+            state.tokens = state.tokens.min(remaining);
+
+            if remaining < 1.0
+                && let Some(reset) = header_f64(headers, RESET_HEADERS)
+            {
+                Self::pause(&mut state, now + reset_delay(reset));
+            }
+        }
+    }
+
+    fn pause(state: &mut RateLimiterState, until: Instant) {
+        state.paused_until = Some(state.paused_until.map_or(until, |p| p.max(until)));
+    }
+
+    fn pause_remaining(state: &RateLimiterState) -> Option<Duration> {
+        state
+            .paused_until
+            .and_then(|until| until.checked_duration_since(Instant::now()))
+            .filter(|d| !d.is_zero())
+    }
+
+    /// Effective bucket capacity, scaled down while throttled.
+    fn capacity(&self, state: &RateLimiterState) -> f64 {
+        (self.config.capacity as f64 * state.rate_factor).max(1.0)
+    }
+
+    /// Tokens added per second.
+    fn refill_rate(&self, state: &RateLimiterState) -> f64 {
+        self.config.capacity as f64 * state.rate_factor / self.config.refill_interval.as_secs_f64()
+    }
+
+    fn recover(&self, state: &mut RateLimiterState, now: Instant) {
+        let Some(adaptive) = self.config.adaptive else {
+            return;
+        };
+        if state.rate_factor >= 1.0 || adaptive.recovery_interval.is_zero() {
+            return;
+        }
+
+        let elapsed = now.duration_since(state.last_adjustment);
+        let steps = (elapsed.as_secs_f64() / adaptive.recovery_interval.as_secs_f64()).floor();
+        if steps >= 1.0 {
+            state.rate_factor = (state.rate_factor + steps * adaptive.recovery_step).min(1.0);
+            state.last_adjustment += adaptive.recovery_interval.mul_f64(steps);
+        }
+    }
+
     fn refill_tokens(&self, state: &mut RateLimiterState) {
         let now = Instant::now();
         let elapsed = now.duration_since(state.last_update);
 
+        if state.paused_until.is_some_and(|until| until <= now) {
+            state.paused_until = None;
+        }
+
         if elapsed > Duration::ZERO {
-            let refill_rate =
-                self.config.capacity as f64 / self.config.refill_interval.as_secs_f64();
-            let new_tokens = elapsed.as_secs_f64() * refill_rate;
+            let new_tokens = elapsed.as_secs_f64() * self.refill_rate(state);
 
-            state.tokens = (state.tokens + new_tokens).min(self.config.capacity as f64);
+            state.tokens = (state.tokens + new_tokens).min(self.capacity(state));
             state.last_update = now;
         }
+
+        self.recover(state, now);
+    }
+}
+
+/// Parses the `Retry-After` header of a response.
+///
+/// Supports both forms allowed by RFC 9110: a number of seconds
+/// (`Retry-After: 120`) and an HTTP date
+/// (`Retry-After: Wed, 21 Oct 2015 07:28:00 GMT`). Dates in the past yield
+/// a zero duration. Delays longer than a day are clamped to a day.
+///
+/// # Example
+///
+/// ```
+/// use booru_rs::ratelimit::parse_retry_after;
+/// use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+/// use std::time::Duration;
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert(RETRY_AFTER, HeaderValue::from_static("30"));
+/// assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(30)));
+/// ```
+#[must_use]
+pub fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(secs) = value.parse::<f64>() {
+        return server_delay(secs);
+    }
+
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(
+        date.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO)
+            .min(MAX_SERVER_DELAY),
+    )
+}
+
+/// Converts a delay in seconds sent by a server, rejecting negative and
+/// non-finite values and clamping huge ones to [`MAX_SERVER_DELAY`].
+fn server_delay(secs: f64) -> Option<Duration> {
+    if secs.is_nan() || secs < 0.0 {
+        return None;
+    }
+
+    Some(
+        Duration::try_from_secs_f64(secs)
+            .unwrap_or(MAX_SERVER_DELAY)
+            .min(MAX_SERVER_DELAY),
+    )
+}
+
+/// Reads the first parseable numeric header out of `names`.
+fn header_f64(headers: &HeaderMap, names: &[&str]) -> Option<f64> {
+    names.iter().find_map(|name| {
+        headers
+            .get(*name)?
+            .to_str()
+            .ok()?
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|v| v.is_finite() && *v >= 0.0)
+    })
+}
+
+/// Interprets a rate limit reset header value.
+///
+/// Sites disagree on whether this is a delay in seconds or a Unix
+/// timestamp, so large values are treated as timestamps.
+fn reset_delay(value: f64) -> Duration {
+    const TIMESTAMP_THRESHOLD: f64 = 1_000_000_000.0;
+
+    if value >= TIMESTAMP_THRESHOLD {
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64();
+        server_delay((value - now).max(0.0)).unwrap_or_default()
+    } else {
+        server_delay(value).unwrap_or_default()
     }
 }
 
@@ -215,6 +601,7 @@ impl std::fmt::Debug for RateLimiter {
         f.debug_struct("RateLimiter")
             .field("capacity", &self.config.capacity)
             .field("refill_interval", &self.config.refill_interval)
+            .field("adaptive", &self.config.adaptive)
             .finish()
     }
 }
@@ -255,4 +642,185 @@ mod tests {
         tokio::time::sleep(Duration::from_millis(150)).await;
         assert!(limiter.available().await >= 10);
     }
+
+    #[tokio::test]
+    async fn test_static_limiter_ignores_observe() {
+        let limiter = RateLimiter::new(10, Duration::from_secs(1));
+
+        limiter
+            .observe(StatusCode::TOO_MANY_REQUESTS, &HeaderMap::new())
+            .await;
+
+        assert_eq!(limiter.current_limit().await, 10.0);
+        assert!(!limiter.status().await.throttled);
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_decrease_on_429() {
+        let limiter = RateLimiter::adaptive(10, Duration::from_secs(1));
+
+        limiter
+            .observe(StatusCode::TOO_MANY_REQUESTS, &HeaderMap::new())
+            .await;
+        assert_eq!(limiter.current_limit().await, 5.0);
+
+        limiter
+            .observe(StatusCode::SERVICE_UNAVAILABLE, &HeaderMap::new())
+            .await;
+        assert_eq!(limiter.current_limit().await, 2.5);
+
+        let status = limiter.status().await;
+        assert!(status.throttled);
+        assert_eq!(status.configured_requests, 10);
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_min_factor() {
+        let limiter = RateLimiter::adaptive(10, Duration::from_secs(1));
+
+        for _ in 0..20 {
+            limiter
+                .observe(StatusCode::TOO_MANY_REQUESTS, &HeaderMap::new())
+                .await;
+        }
+
+        assert!((limiter.current_limit().await - 0.5).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_config_is_clamped() {
+        let limiter =
+            RateLimiter::new(10, Duration::from_secs(1)).with_adaptive_config(AdaptiveConfig {
+                decrease_factor: 0.0,
+                min_factor: 0.0,
+                recovery_step: f64::NAN,
+                ..AdaptiveConfig::default()
+            });
+
+        for _ in 0..10 {
+            limiter.acquire().await;
+        }
+        limiter
+            .observe(StatusCode::TOO_MANY_REQUESTS, &HeaderMap::new())
+            .await;
+        assert!((limiter.current_limit().await - 0.01).abs() < 1e-9);
+
+        // Waiting for a token at the lowest rate must not panic
+        let acquired = tokio::time::timeout(Duration::from_millis(20), limiter.acquire()).await;
+        assert!(acquired.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_recovery() {
+        let limiter =
+            RateLimiter::new(10, Duration::from_secs(1)).with_adaptive_config(AdaptiveConfig {
+                recovery_interval: Duration::from_millis(20),
+                recovery_step: 0.25,
+                ..AdaptiveConfig::default()
+            });
+
+        limiter
+            .observe(StatusCode::TOO_MANY_REQUESTS, &HeaderMap::new())
+            .await;
+        assert_eq!(limiter.current_limit().await, 5.0);
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(limiter.current_limit().await, 10.0);
+        assert!(!limiter.status().await.throttled);
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_pauses_limiter() {
+        let limiter = RateLimiter::adaptive(10, Duration::from_millis(10));
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, "60".parse().unwrap());
+
+        limiter
+            .observe(StatusCode::TOO_MANY_REQUESTS, &headers)
+            .await;
+
+        assert!(!limiter.try_acquire().await);
+        let paused = limiter.status().await.paused_for.unwrap();
+        assert!(paused > Duration::from_secs(59));
+    }
+
+    #[tokio::test]
+    async fn test_sync_remaining_from_headers() {
+        let limiter = RateLimiter::adaptive(10, Duration::from_secs(60));
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", "3".parse().unwrap());
+
+        limiter.observe(StatusCode::OK, &headers).await;
+
+        assert_eq!(limiter.available().await, 3);
+        assert!(!limiter.status().await.throttled);
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_quota_pauses_until_reset() {
+        let limiter = RateLimiter::adaptive(10, Duration::from_secs(1));
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", "0".parse().unwrap());
+        headers.insert("x-ratelimit-reset", "30".parse().unwrap());
+
+        limiter.observe(StatusCode::OK, &headers).await;
+
+        assert!(!limiter.try_acquire().await);
+        assert!(limiter.status().await.paused_for.is_some());
+    }
+
+    #[test]
+    fn test_parse_retry_after_seconds() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, "5".parse().unwrap());
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(5)));
+
+        headers.insert(RETRY_AFTER, "soon".parse().unwrap());
+        assert_eq!(parse_retry_after(&headers), None);
+    }
+
+    #[test]
+    fn test_server_delays_are_clamped() {
+        let mut headers = HeaderMap::new();
+
+        for huge in ["1e20", "inf", "18446744073709551616"] {
+            headers.insert(RETRY_AFTER, huge.parse().unwrap());
+            assert_eq!(parse_retry_after(&headers), Some(MAX_SERVER_DELAY));
+        }
+        for garbage in ["-5", "NaN", "-inf"] {
+            headers.insert(RETRY_AFTER, garbage.parse().unwrap());
+            assert_eq!(parse_retry_after(&headers), None);
+        }
+
+        headers.insert(
+            RETRY_AFTER,
+            "Fri, 31 Dec 9999 23:59:59 GMT".parse().unwrap(),
+        );
+        assert_eq!(parse_retry_after(&headers), Some(MAX_SERVER_DELAY));
+
+        assert_eq!(reset_delay(1e300), MAX_SERVER_DELAY);
+        assert_eq!(reset_delay(f64::INFINITY), MAX_SERVER_DELAY);
+    }
+
+    #[test]
+    fn test_parse_retry_after_http_date() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            RETRY_AFTER,
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+
+        // Dates in the past mean "retry now"
+        assert_eq!(parse_retry_after(&headers), Some(Duration::ZERO));
+
+        let future = SystemTime::now() + Duration::from_secs(120);
+        headers.insert(
+            RETRY_AFTER,
+            httpdate::fmt_http_date(future).parse().unwrap(),
+        );
+        let delay = parse_retry_after(&headers).unwrap();
+        assert!(delay > Duration::from_secs(100) && delay <= Duration::from_secs(120));
+    }
 }
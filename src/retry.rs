@@ -2,8 +2,41 @@
 //!
 //! This module provides utilities for automatically retrying failed requests
 //! with exponential backoff delays.
+//!
+//! Delays can be randomized with [`Jitter`] to avoid synchronized retry
+//! storms, server-provided `Retry-After` values are honoured, and a shared
+//! [`RetryBudget`] caps the number of retries so that an outage does not
+//! amplify traffic.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use booru_rs::prelude::*;
+//! use booru_rs::retry::{Jitter, RetryBudget, RetryConfig, with_retry};
+//!
+//! # async fn example() -> Result<()> {
+//! let budget = RetryBudget::new(10, 0.1);
+//! let config = RetryConfig::new(5)
+//!     .with_jitter(Jitter::Full)
+//!     .with_budget(budget)
+//!     .on_retry(|event| {
+//!         eprintln!(
+//!             "retry #{} in {:?}: {}",
+//!             event.attempt, event.delay, event.error
+//!         );
+//!     });
+//!
+//! let client = SafebooruClient::builder().tag("landscape")?.build();
+//! let posts = with_retry(config, || client.get()).await?;
+//! # Ok(())
+//! # }
+//! ```
 
+use std::collections::hash_map::RandomState;
 use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use crate::error::{BooruError, Result};
@@ -12,9 +45,112 @@ use crate::error::{BooruError, Result};
 pub const DEFAULT_MAX_RETRIES: u32 = 3;
 pub const DEFAULT_INITIAL_DELAY_MS: u64 = 100;
 pub const DEFAULT_MAX_DELAY_MS: u64 = 5000;
+pub const DEFAULT_MAX_RETRY_AFTER_SECS: u64 = 60;
 
-/// Configuration for retry behavior.
+/// Randomization applied to retry delays.
+///
+/// See the AWS Architecture Blog post
+/// ["Exponential Backoff And Jitter"](https://aws.amazon.com/blogs/architecture/exponential-backoff-and-jitter/)
+/// for a comparison of the strategies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Jitter {
+    /// Use the exact exponential backoff delay.
+    #[default]
+    None,
+    /// Pick a random delay between zero and the exponential backoff delay.
+    Full,
+    /// Pick a random delay between the initial delay and three times the
+    /// previous delay, capped at the maximum delay.
+    Decorrelated,
+}
+
+/// Information about a retry, passed to the [`RetryConfig::on_retry`] hook.
+#[derive(Debug)]
+pub struct RetryEvent<'a> {
+    /// The retry attempt number, starting at 1.
+    pub attempt: u32,
+    /// How long we will wait before retrying.
+    pub delay: Duration,
+    /// The error that caused the retry.
+    pub error: &'a BooruError,
+}
+
+/// A callback type for retry notifications.
+pub type RetryCallback = Arc<dyn Fn(&RetryEvent<'_>) + Send + Sync>;
+
+/// A token bucket limiting how many retries may be made.
+///
+/// Every retry withdraws one token and every successful operation deposits
+/// a fraction of a token, so retries can only make up a bounded share of
+/// the traffic. When a site goes down and nothing succeeds, the budget runs
+/// dry and failures are returned immediately instead of being retried.
+///
+/// The budget is `Clone` and shares its state, so one budget can be used
+/// across many [`RetryConfig`]s and tasks.
+///
+/// # Example
+///
+/// ```
+/// use booru_rs::retry::RetryBudget;
+///
+/// // Allow bursts of 10 retries; every 10 successes earn one more.
+/// let budget = RetryBudget::new(10, 0.1);
+/// assert_eq!(budget.available(), 10.0);
+/// ```
 #[derive(Debug, Clone)]
+pub struct RetryBudget {
+    tokens: Arc<Mutex<f64>>,
+    max_tokens: f64,
+    deposit: f64,
+}
+
+impl RetryBudget {
+    /// Creates a new, full retry budget.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_tokens` - Maximum number of retries that can be banked
+    /// * `deposit_per_success` - Tokens earned by each successful operation
+    #[must_use]
+    pub fn new(max_tokens: u32, deposit_per_success: f64) -> Self {
+        Self {
+            tokens: Arc::new(Mutex::new(max_tokens as f64)),
+            max_tokens: max_tokens as f64,
+            deposit: deposit_per_success,
+        }
+    }
+
+    /// Returns the number of retries currently available.
+    #[must_use]
+    pub fn available(&self) -> f64 {
+        *self.lock()
+    }
+
+    /// Withdraws a token for a retry, returning `false` if the budget is empty.
+    pub fn try_withdraw(&self) -> bool {
+        let mut tokens = self.lock();
+        if *tokens >= 1.0 {
+            *tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Records a successful operation.
+    pub fn deposit(&self) {
+        let mut tokens = self.lock();
+        *tokens = (*tokens + self.deposit).min(self.max_tokens);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, f64> {
+        self.tokens.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Configuration for retry behavior.
+#[derive(Clone)]
 pub struct RetryConfig {
     /// Maximum number of retry attempts (0 = no retries).
     pub max_retries: u32,
@@ -24,6 +160,15 @@ pub struct RetryConfig {
     pub max_delay: Duration,
     /// Multiplier applied to delay after each retry (for exponential backoff).
     pub backoff_factor: f64,
+    /// Randomization applied to the backoff delay.
+    pub jitter: Jitter,
+    /// Longest `Retry-After` we are willing to wait. If the server asks for
+    /// more, the error is returned instead of retrying.
+    pub max_retry_after: Duration,
+    /// Shared budget limiting the number of retries.
+    pub budget: Option<RetryBudget>,
+    /// Hook called before every retry.
+    pub on_retry: Option<RetryCallback>,
 }
 
 impl Default for RetryConfig {
@@ -33,10 +178,29 @@ impl Default for RetryConfig {
             initial_delay: Duration::from_millis(DEFAULT_INITIAL_DELAY_MS),
             max_delay: Duration::from_millis(DEFAULT_MAX_DELAY_MS),
             backoff_factor: 2.0,
+            jitter: Jitter::None,
+            max_retry_after: Duration::from_secs(DEFAULT_MAX_RETRY_AFTER_SECS),
+            budget: None,
+            on_retry: None,
         }
     }
 }
 
+impl std::fmt::Debug for RetryConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryConfig")
+            .field("max_retries", &self.max_retries)
+            .field("initial_delay", &self.initial_delay)
+            .field("max_delay", &self.max_delay)
+            .field("backoff_factor", &self.backoff_factor)
+            .field("jitter", &self.jitter)
+            .field("max_retry_after", &self.max_retry_after)
+            .field("budget", &self.budget)
+            .field("on_retry", &self.on_retry.is_some())
+            .finish()
+    }
+}
+
 impl RetryConfig {
     /// Creates a new retry configuration with the specified max retries.
     #[must_use]
@@ -77,6 +241,59 @@ impl RetryConfig {
         self
     }
 
+    /// Sets the jitter strategy.
+    #[must_use]
+    pub fn with_jitter(mut self, jitter: Jitter) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets the longest `Retry-After` delay that will be honoured.
+    #[must_use]
+    pub fn with_max_retry_after(mut self, delay: Duration) -> Self {
+        self.max_retry_after = delay;
+        self
+    }
+
+    /// Sets a shared retry budget.
+    #[must_use]
+    pub fn with_budget(mut self, budget: RetryBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Sets a hook called before every retry, e.g. for logging.
+    #[must_use]
+    pub fn on_retry<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&RetryEvent<'_>) + Send + Sync + 'static,
+    {
+        self.on_retry = Some(Arc::new(callback));
+        self
+    }
+
+    /// Calculates the delay before retry `attempt`, given the previous delay.
+    ///
+    /// A server-provided `Retry-After` takes precedence over the backoff.
+    /// Returns `None` if the server asked us to wait longer than
+    /// [`max_retry_after`](Self::max_retry_after).
+    fn next_delay(&self, attempt: u32, previous: Duration, error: &BooruError) -> Option<Duration> {
+        if let Some(retry_after) = error.retry_after() {
+            return (retry_after <= self.max_retry_after).then_some(retry_after);
+        }
+
+        let delay = match self.jitter {
+            Jitter::None => self.delay_for_attempt(attempt),
+            Jitter::Full => self.delay_for_attempt(attempt).mul_f64(random_unit()),
+            Jitter::Decorrelated => {
+                let low = self.initial_delay.as_secs_f64();
+                let high = (previous.as_secs_f64() * 3.0).max(low);
+                Duration::from_secs_f64(low + (high - low) * random_unit()).min(self.max_delay)
+            }
+        };
+        Some(delay)
+    }
+
     /// Calculates the delay for a given attempt number.
     fn delay_for_attempt(&self, attempt: u32) -> Duration {
         if attempt == 0 {
@@ -91,6 +308,29 @@ impl RetryConfig {
     }
 }
 
+/// Returns a uniformly distributed random number in `[0, 1)`.
+///
+/// Jitter doesn't need cryptographic randomness, so this uses the randomly
+/// seeded std hasher rather than pulling in a dependency.
+pub(crate) fn random_unit() -> f64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Determines if an HTTP status code indicates a transient failure.
+///
+/// Server errors (5xx), `429 Too Many Requests` and `408 Request Timeout`
+/// are retryable.
+#[must_use]
+pub fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    status.is_server_error()
+        || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        || status == reqwest::StatusCode::REQUEST_TIMEOUT
+}
+
 /// Determines if an error is retryable.
 ///
 /// Only transient network errors should be retried. Parse errors,
@@ -104,7 +344,7 @@ pub fn is_retryable(error: &BooruError) -> bool {
             }
             // Check for server errors (5xx) which are retryable
             if let Some(status) = e.status() {
-                return status.is_server_error();
+                return is_retryable_status(status);
             }
             // Retry on other transient request errors
             e.is_request()
@@ -117,13 +357,26 @@ pub fn is_retryable(error: &BooruError) -> bool {
         BooruError::InvalidUrl(_) => false,
         BooruError::Unauthorized(_) => false,
         BooruError::InvalidTag { .. } => false,
-        BooruError::RateLimited => true, // Rate limit errors can be retried after waiting
-        BooruError::Io(_) => false,      // I/O errors are generally not retryable
+        BooruError::InvalidDate(_) => false,
+        BooruError::RateLimited { .. } => true, // Rate limit errors can be retried after waiting
+        BooruError::CircuitOpen { .. } => false, // Fail fast while the host is down
+        BooruError::Forbidden(_) => false,
+        BooruError::DuplicatePost { .. } => false,
+        BooruError::ServerError { .. } => true,
+        BooruError::ApiError { status, .. } => reqwest::StatusCode::from_u16(*status)
+            .map(is_retryable_status)
+            .unwrap_or(false),
+        BooruError::Io(_) => false, // I/O errors are generally not retryable
     }
 }
 
 /// Executes an async operation with retry logic.
 ///
+/// Retries stop when `max_retries` is reached, when the error is not
+/// retryable, when the server asks to wait longer than `max_retry_after`,
+/// or when the configured [`RetryBudget`] is exhausted. In all cases the
+/// last error is returned.
+///
 /// # Example
 ///
 /// ```ignore
@@ -140,23 +393,43 @@ where
     Fut: Future<Output = Result<T>>,
 {
     let mut attempt = 0;
-    let mut last_error;
+    let mut previous_delay = config.initial_delay;
 
     loop {
         match operation().await {
-            Ok(result) => return Ok(result),
+            Ok(result) => {
+                if let Some(budget) = &config.budget {
+                    budget.deposit();
+                }
+                return Ok(result);
+            }
             Err(e) => {
-                last_error = e;
-
                 // Check if we should retry
-                if attempt >= config.max_retries || !is_retryable(&last_error) {
-                    return Err(last_error);
+                if attempt >= config.max_retries || !is_retryable(&e) {
+                    return Err(e);
+                }
+
+                let Some(delay) = config.next_delay(attempt + 1, previous_delay, &e) else {
+                    return Err(e);
+                };
+
+                if let Some(budget) = &config.budget
+                    && !budget.try_withdraw()
+                {
+                    return Err(e);
                 }
 
                 attempt += 1;
 
-                // Calculate delay with exponential backoff
-                let delay = config.delay_for_attempt(attempt);
+                if let Some(callback) = &config.on_retry {
+                    callback(&RetryEvent {
+                        attempt,
+                        delay,
+                        error: &e,
+                    });
+                }
+
+                previous_delay = delay;
                 tokio::time::sleep(delay).await;
             }
         }
@@ -185,4 +458,136 @@ mod tests {
         assert_eq!(config.delay_for_attempt(2), Duration::from_millis(150)); // Capped
         assert_eq!(config.delay_for_attempt(3), Duration::from_millis(150)); // Capped
     }
+
+    #[test]
+    fn test_full_jitter_bounds() {
+        let config = RetryConfig::default().with_jitter(Jitter::Full);
+        let error = BooruError::EmptyResponse;
+
+        for attempt in 1..=5 {
+            let delay = config.next_delay(attempt, Duration::ZERO, &error).unwrap();
+            assert!(delay <= config.delay_for_attempt(attempt));
+        }
+    }
+
+    #[test]
+    fn test_decorrelated_jitter_bounds() {
+        let config = RetryConfig::default()
+            .with_jitter(Jitter::Decorrelated)
+            .with_max_delay(Duration::from_millis(1000));
+        let error = BooruError::EmptyResponse;
+
+        let mut previous = config.initial_delay;
+        for attempt in 1..=10 {
+            let delay = config.next_delay(attempt, previous, &error).unwrap();
+            assert!(delay >= config.initial_delay);
+            assert!(delay <= Duration::from_millis(1000));
+            assert!(delay <= previous * 3);
+            previous = delay;
+        }
+    }
+
+    #[test]
+    fn test_retry_after_takes_precedence() {
+        let config = RetryConfig::default().with_max_retry_after(Duration::from_secs(10));
+
+        let error = BooruError::RateLimited {
+            retry_after: Some(Duration::from_secs(3)),
+        };
+        assert_eq!(
+            config.next_delay(1, Duration::ZERO, &error),
+            Some(Duration::from_secs(3))
+        );
+
+        let error = BooruError::RateLimited {
+            retry_after: Some(Duration::from_secs(30)),
+        };
+        assert_eq!(config.next_delay(1, Duration::ZERO, &error), None);
+    }
+
+    #[test]
+    fn test_retryable_status() {
+        use reqwest::StatusCode;
+
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND));
+        assert!(!is_retryable_status(StatusCode::UNAUTHORIZED));
+    }
+
+    #[test]
+    fn test_budget() {
+        let budget = RetryBudget::new(2, 0.5);
+
+        assert!(budget.try_withdraw());
+        assert!(budget.try_withdraw());
+        assert!(!budget.try_withdraw());
+
+        budget.deposit();
+        budget.deposit();
+        assert!(budget.try_withdraw());
+
+        for _ in 0..10 {
+            budget.deposit();
+        }
+        assert_eq!(budget.available(), 2.0);
+    }
+
+    #[tokio::test]
+    async fn test_with_retry_reports_attempts() {
+        use std::sync::atomic::AtomicU32;
+
+        let calls = Arc::new(AtomicU32::new(0));
+        let events = Arc::new(Mutex::new(Vec::new()));
+
+        let config = RetryConfig::new(3)
+            .with_initial_delay(Duration::from_millis(1))
+            .on_retry({
+                let events = events.clone();
+                move |event| events.lock().unwrap().push((event.attempt, event.delay))
+            });
+
+        let result: Result<()> = with_retry(config, || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async {
+                Err(BooruError::RateLimited {
+                    retry_after: Some(Duration::from_millis(2)),
+                })
+            }
+        })
+        .await;
+
+        assert!(matches!(result, Err(BooruError::RateLimited { .. })));
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                (1, Duration::from_millis(2)),
+                (2, Duration::from_millis(2)),
+                (3, Duration::from_millis(2)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_with_retry_stops_when_budget_exhausted() {
+        use std::sync::atomic::AtomicU32;
+
+        let calls = Arc::new(AtomicU32::new(0));
+        let budget = RetryBudget::new(1, 0.1);
+        let config = RetryConfig::new(5)
+            .with_initial_delay(Duration::from_millis(1))
+            .with_budget(budget.clone());
+
+        let result: Result<()> = with_retry(config, || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async { Err(BooruError::RateLimited { retry_after: None }) }
+        })
+        .await;
+
+        assert!(result.is_err());
+        // One initial attempt plus the single retry the budget allowed
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert!(!budget.try_withdraw());
+    }
 }
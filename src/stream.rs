@@ -2,11 +2,92 @@
 //!
 //! This module provides utilities for iterating through paginated
 //! booru results using async streams.
+//!
+//! Any paginated endpoint can be streamed by implementing [`Paginate`].
+//! Every [`Client`] implements it for post searches.
+
+use std::future::Future;
 
-use crate::client::{Client, ClientBuilder};
+use crate::client::{Client, ClientBuilder, PostPage};
 use crate::error::Result;
 
-/// An async stream that yields pages of posts.
+/// A paginated API endpoint.
+///
+/// Implementors describe how to fetch one page of results for a query;
+/// [`PageStream`] and [`PostStream`] take care of walking the pages.
+pub trait Paginate {
+    /// The type of item on each page.
+    type Item: Send;
+
+    /// The query that is re-sent for every page.
+    type Query: Clone + Send + Sync;
+
+    /// Returns the page the query starts at.
+    fn first_page(query: &Self::Query) -> u32;
+
+    /// Fetches a single page of results.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or if the response cannot be parsed.
+    fn fetch_page(
+        query: &Self::Query,
+        page: u32,
+    ) -> impl Future<Output = Result<Vec<Self::Item>>> + Send;
+
+    /// Fetches a single page of results, with the total number of results
+    /// if the endpoint reports it.
+    ///
+    /// Streams call this until a total is reported, then count down from it
+    /// with [`fetch_page`](Self::fetch_page) and stop once it is reached.
+    /// They always stop at an empty page. The default implementation wraps
+    /// [`fetch_page`](Self::fetch_page) and reports no total.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or if the response cannot be parsed.
+    fn fetch_page_with_total(
+        query: &Self::Query,
+        page: u32,
+    ) -> impl Future<Output = Result<PostPage<Self::Item>>> + Send {
+        let items = Self::fetch_page(query, page);
+
+        async move { items.await.map(PostPage::from) }
+    }
+}
+
+impl<T: Client> Paginate for T {
+    type Item = T::Post;
+    type Query = ClientBuilder<T>;
+
+    fn first_page(query: &Self::Query) -> u32 {
+        query.page
+    }
+
+    fn fetch_page(
+        query: &Self::Query,
+        page: u32,
+    ) -> impl Future<Output = Result<Vec<Self::Item>>> + Send {
+        let mut builder = query.clone();
+        builder.page = page;
+        let client = builder.build();
+
+        async move { client.get().await }
+    }
+
+    fn fetch_page_with_total(
+        query: &Self::Query,
+        page: u32,
+    ) -> impl Future<Output = Result<PostPage<Self::Item>>> + Send {
+        let mut builder = query.clone();
+        builder.page = page;
+        let client = builder.build();
+
+        async move { client.get_page().await }
+    }
+}
+
+/// An async stream that yields pages of results.
 ///
 /// Created by [`ClientBuilder::into_page_stream`] or [`ClientBuilder::into_post_stream`].
 ///
@@ -33,22 +114,28 @@ use crate::error::Result;
 /// # Ok(())
 /// # }
 /// ```
-pub struct PageStream<T: Client> {
-    builder: ClientBuilder<T>,
+pub struct PageStream<P: Paginate> {
+    query: P::Query,
+    first_page: u32,
     current_page: u32,
     exhausted: bool,
     max_pages: Option<u32>,
+    total: Option<u64>,
+    remaining: Option<u64>,
 }
 
-impl<T: Client> PageStream<T> {
-    /// Creates a new page stream from a client builder.
-    pub fn new(builder: ClientBuilder<T>) -> Self {
-        let current_page = builder.page;
+impl<P: Paginate> PageStream<P> {
+    /// Creates a new page stream from a query, such as a client builder.
+    pub fn new(query: P::Query) -> Self {
+        let first_page = P::first_page(&query);
         Self {
-            builder,
-            current_page,
+            query,
+            first_page,
+            current_page: first_page,
             exhausted: false,
             max_pages: None,
+            total: None,
+            remaining: None,
         }
     }
 
@@ -64,36 +151,64 @@ impl<T: Client> PageStream<T> {
         self.current_page
     }
 
+    /// Returns the total number of results, as reported with the first page
+    /// that had one.
+    ///
+    /// Only some sites report it; see [`Client::get_page`].
+    pub fn total(&self) -> Option<u64> {
+        self.total
+    }
+
+    /// Returns the number of results after the latest page, if the site
+    /// reports a total.
+    pub fn remaining(&self) -> Option<u64> {
+        self.remaining
+    }
+
     /// Fetches the next page of results.
     ///
-    /// Returns `None` when there are no more pages or the max page limit is reached.
-    pub async fn next(&mut self) -> Option<Result<Vec<T::Post>>> {
+    /// Returns `None` when there are no more pages or the max page limit is
+    /// reached. If the site reports a total, the stream ends after the page
+    /// that reaches it; it always ends with an empty page.
+    pub async fn next(&mut self) -> Option<Result<Vec<P::Item>>> {
         if self.exhausted {
             return None;
         }
 
         // Check max pages limit
         if let Some(max) = self.max_pages {
-            let pages_fetched = self.current_page.saturating_sub(self.builder.page);
+            let pages_fetched = self.current_page.saturating_sub(self.first_page);
             if pages_fetched >= max {
                 self.exhausted = true;
                 return None;
             }
         }
 
-        // Build client for current page
-        let mut page_builder = self.builder.clone();
-        page_builder.page = self.current_page;
-        let client = page_builder.build();
+        // The total is only asked for until a page reports it
+        let page = if self.total.is_none() {
+            P::fetch_page_with_total(&self.query, self.current_page).await
+        } else {
+            P::fetch_page(&self.query, self.current_page)
+                .await
+                .map(PostPage::from)
+        };
 
-        match client.get().await {
-            Ok(posts) => {
-                if posts.is_empty() {
+        match page {
+            Ok(page) => {
+                if page.total.is_some() {
+                    self.total = page.total;
+                    self.remaining = page.remaining();
+                } else if let Some(remaining) = &mut self.remaining {
+                    *remaining = remaining.saturating_sub(page.posts.len() as u64);
+                }
+
+                if page.posts.is_empty() || self.remaining == Some(0) {
                     self.exhausted = true;
-                    return Some(Ok(posts));
                 }
-                self.current_page += 1;
-                Some(Ok(posts))
+                if !page.posts.is_empty() {
+                    self.current_page += 1;
+                }
+                Some(Ok(page.posts))
             }
             Err(e) => {
                 self.exhausted = true;
@@ -103,10 +218,10 @@ impl<T: Client> PageStream<T> {
     }
 }
 
-/// An async stream that yields individual posts across pages.
+/// An async stream that yields individual posts (or other items) across pages.
 ///
 /// This stream automatically handles pagination, fetching new pages
-/// as needed while yielding posts one at a time.
+/// as needed while yielding items one at a time.
 ///
 /// # Example
 ///
@@ -130,23 +245,23 @@ impl<T: Client> PageStream<T> {
 /// # Ok(())
 /// # }
 /// ```
-pub struct PostStream<T: Client> {
-    page_stream: PageStream<T>,
-    buffer: Vec<T::Post>,
-    buffer_index: usize,
+pub struct PostStream<P: Paginate> {
+    page_stream: PageStream<P>,
+    buffer: std::vec::IntoIter<P::Item>,
     posts_yielded: u32,
     max_posts: Option<u32>,
+    total: Option<u64>,
 }
 
-impl<T: Client> PostStream<T> {
-    /// Creates a new post stream from a client builder.
-    pub fn new(builder: ClientBuilder<T>) -> Self {
+impl<P: Paginate> PostStream<P> {
+    /// Creates a new item stream from a query, such as a client builder.
+    pub fn new(query: P::Query) -> Self {
         Self {
-            page_stream: PageStream::new(builder),
-            buffer: Vec::new(),
-            buffer_index: 0,
+            page_stream: PageStream::new(query),
+            buffer: Vec::new().into_iter(),
             posts_yielded: 0,
             max_posts: None,
+            total: None,
         }
     }
 
@@ -164,6 +279,74 @@ impl<T: Client> PostStream<T> {
         self
     }
 
+    /// Sets the total number of posts the stream will find, if known.
+    ///
+    /// The total is only used for [`size_hint`](Self::size_hint); it does
+    /// not limit the stream. For Danbooru, it can be obtained with
+    /// [`ClientBuilder::count`](crate::client::ClientBuilder#method.count).
+    /// Gelbooru-based sites report it with the first page, so it is not needed
+    /// there.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use booru_rs::prelude::*;
+    ///
+    /// # async fn example() -> Result<()> {
+    /// let builder = DanbooruClient::builder().tag("cat_ears")?;
+    /// let total = builder.count().await?;
+    ///
+    /// let mut stream = builder.into_post_stream().with_total(total);
+    /// while let Some(post) = stream.next().await {
+    ///     let post = post?;
+    ///     let (remaining, _) = stream.size_hint();
+    ///     println!("#{} ({} left)", post.id, remaining);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_total(mut self, total: u64) -> Self {
+        self.total = Some(total);
+        self
+    }
+
+    /// Returns the total set with [`with_total`](Self::with_total), or else
+    /// the total the site reported with the latest page.
+    pub fn total(&self) -> Option<u64> {
+        self.total.or_else(|| self.page_stream.total())
+    }
+
+    /// Returns the estimated number of posts left, like [`Iterator::size_hint`].
+    ///
+    /// The estimate is derived from the total set with
+    /// [`with_total`](Self::with_total), or else the total reported by the
+    /// site, and from `max_posts`. Without any of them, the upper bound is
+    /// unknown. The lower bound is the number of
+    /// already fetched posts that have not been yielded yet.
+    pub fn size_hint(&self) -> (usize, Option<usize>) {
+        let yielded = u64::from(self.posts_yielded);
+        let from_total = match self.total {
+            Some(total) => Some(total.saturating_sub(yielded)),
+            None => self
+                .page_stream
+                .remaining()
+                .map(|remaining| remaining + self.buffer.len() as u64),
+        };
+        let from_max = self
+            .max_posts
+            .map(|max| u64::from(max).saturating_sub(yielded));
+
+        let upper = match (from_total, from_max) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        let upper = upper.map(|n| usize::try_from(n).unwrap_or(usize::MAX));
+        let lower = upper.map_or(self.buffer.len(), |upper| self.buffer.len().min(upper));
+
+        (lower, upper)
+    }
+
     /// Returns the number of posts yielded so far.
     pub fn posts_yielded(&self) -> u32 {
         self.posts_yielded
@@ -177,7 +360,7 @@ impl<T: Client> PostStream<T> {
     /// Fetches the next post.
     ///
     /// Returns `None` when there are no more posts.
-    pub async fn next(&mut self) -> Option<Result<T::Post>> {
+    pub async fn next(&mut self) -> Option<Result<P::Item>> {
         // Check max posts limit
         if let Some(max) = self.max_posts
             && self.posts_yielded >= max
@@ -186,10 +369,7 @@ impl<T: Client> PostStream<T> {
         }
 
         // If we have posts in the buffer, return the next one
-        if self.buffer_index < self.buffer.len() {
-            let post = self.buffer.swap_remove(self.buffer_index);
-            // Note: swap_remove changes order but we're consuming, so OK
-            self.buffer_index = 0; // Reset since swap_remove moves last to current
+        if let Some(post) = self.buffer.next() {
             self.posts_yielded += 1;
             return Some(Ok(post));
         }
@@ -197,19 +377,10 @@ impl<T: Client> PostStream<T> {
         // Need to fetch more posts
         match self.page_stream.next().await? {
             Ok(posts) => {
-                if posts.is_empty() {
-                    return None;
-                }
-                self.buffer = posts;
-                self.buffer_index = 1; // Will return index 0
+                self.buffer = posts.into_iter();
+                let post = self.buffer.next()?;
                 self.posts_yielded += 1;
-
-                // Pop the first post
-                if self.buffer.is_empty() {
-                    None
-                } else {
-                    Some(Ok(self.buffer.swap_remove(0)))
-                }
+                Some(Ok(post))
             }
             Err(e) => Some(Err(e)),
         }
@@ -222,7 +393,7 @@ impl<T: Client> PostStream<T> {
     /// # Errors
     ///
     /// Returns the first error encountered during pagination.
-    pub async fn collect(mut self) -> Result<Vec<T::Post>> {
+    pub async fn collect(mut self) -> Result<Vec<P::Item>> {
         let mut all_posts = Vec::new();
 
         while let Some(result) = self.next().await {
@@ -291,3 +462,74 @@ impl<T: Client> ClientBuilder<T> {
         PostStream::new(self)
     }
 }
+
+/// Implements the paging half of a search query: the `limit()` and `page()`
+/// setters, `into_page_stream()`, a per-item stream method, and
+/// [`Paginate`] on top of the query's own `get()`.
+///
+/// The query must have `limit: u32` and `page: u32` fields and an
+/// `async fn get(&self) -> Result<Vec<Item>>`.
+#[cfg(any(feature = "danbooru", feature = "gelbooru"))]
+macro_rules! paginated_query {
+    (
+        $query:ty => $item:ty,
+        noun: $noun:literal,
+        stream: $stream:ident,
+        limit: $limit_doc:literal,
+        page: $page_doc:literal $(,)?
+    ) => {
+        impl $query {
+            #[doc = concat!("Sets the maximum number of ", $noun, " per page.")]
+            ///
+            #[doc = $limit_doc]
+            #[must_use]
+            pub fn limit(mut self, limit: u32) -> Self {
+                self.limit = limit;
+                self
+            }
+
+            /// Sets the page number.
+            ///
+            #[doc = $page_doc]
+            #[must_use]
+            pub fn page(mut self, page: u32) -> Self {
+                self.page = page;
+                self
+            }
+
+            #[doc = concat!("Creates an async stream that yields pages of ", $noun, ".")]
+            #[must_use]
+            pub fn into_page_stream(self) -> $crate::stream::PageStream<Self> {
+                $crate::stream::PageStream::new(self)
+            }
+
+            #[doc = concat!("Creates an async stream that yields individual ", $noun, " across pages.")]
+            #[must_use]
+            pub fn $stream(self) -> $crate::stream::PostStream<Self> {
+                $crate::stream::PostStream::new(self)
+            }
+        }
+
+        impl $crate::stream::Paginate for $query {
+            type Item = $item;
+            type Query = Self;
+
+            fn first_page(query: &Self) -> u32 {
+                query.page
+            }
+
+            fn fetch_page(
+                query: &Self,
+                page: u32,
+            ) -> impl ::std::future::Future<Output = $crate::error::Result<Vec<$item>>> + Send
+            {
+                let query = query.clone().page(page);
+
+                async move { query.get().await }
+            }
+        }
+    };
+}
+
+#[cfg(any(feature = "danbooru", feature = "gelbooru"))]
+pub(crate) use paginated_query;
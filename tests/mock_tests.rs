@@ -150,6 +150,67 @@ fn danbooru_posts_json() -> &'static str {
     ]"#
 }
 
+/// Test fixture for a single Gelbooru post
+fn gelbooru_post_json() -> &'static str {
+    r#"{
+        "id": 9876543,
+        "created_at": "Mon Dec 23 12:00:00 -0600 2024",
+        "score": 150,
+        "width": 1920,
+        "height": 1080,
+        "md5": "gelbooru123abc",
+        "file_url": "https://example.com/images/ge/lb/gelbooru123abc.jpg",
+        "tags": "cat_ears blue_eyes 1girl",
+        "image": "gelbooru123abc.jpg",
+        "source": "",
+        "rating": "general"
+    }"#
+}
+
+/// Test fixture for a single Rule34 post
+fn rule34_post_json() -> &'static str {
+    r#"{
+        "id": 15000000,
+        "score": 75,
+        "width": 900,
+        "height": 1200,
+        "file_url": "https://example.com/images/3900/rule34hash123.png",
+        "preview_url": "https://example.com/thumbnails/3900/thumbnail_rule34hash123.jpg",
+        "sample_url": "https://example.com/samples/3900/sample_rule34hash123.jpg",
+        "tags": "1girl blue_hair",
+        "rating": "explicit"
+    }"#
+}
+
+/// Returns a fixture post with some fields replaced, for tests that only
+/// care about a few of them. Array fixtures use their first post.
+fn post_with(fixture: &str, fields: serde_json::Value) -> serde_json::Value {
+    let mut post: serde_json::Value = serde_json::from_str(fixture).unwrap();
+    if let serde_json::Value::Array(posts) = post {
+        post = posts.into_iter().next().unwrap();
+    }
+    for (key, value) in fields.as_object().unwrap() {
+        post[key] = value.clone();
+    }
+    post
+}
+
+fn danbooru_post(fields: serde_json::Value) -> serde_json::Value {
+    post_with(danbooru_post_json(), fields)
+}
+
+fn gelbooru_post(fields: serde_json::Value) -> serde_json::Value {
+    post_with(gelbooru_post_json(), fields)
+}
+
+fn rule34_post(fields: serde_json::Value) -> serde_json::Value {
+    post_with(rule34_post_json(), fields)
+}
+
+fn safebooru_post(fields: serde_json::Value) -> serde_json::Value {
+    post_with(safebooru_posts_json(), fields)
+}
+
 mod mock_safebooru {
     use super::*;
     use booru_rs::prelude::*;
@@ -294,6 +355,10 @@ mod mock_safebooru {
         let result = client.get().await;
 
         assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            BooruError::ServerError { status: 500 }
+        ));
     }
 
     #[tokio::test]
@@ -315,6 +380,27 @@ mod mock_safebooru {
         // Invalid JSON causes a Request error (reqwest's json parsing)
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_post_not_found_404() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .and(query_param("id", "99999"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("Not Found"))
+            .mount(&mock_server)
+            .await;
+
+        let client = SafebooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build();
+
+        assert!(matches!(
+            client.get_by_id(99999).await,
+            Err(BooruError::PostNotFound(99999))
+        ));
+    }
 }
 
 mod mock_danbooru {
@@ -406,6 +492,84 @@ mod mock_danbooru {
         let result = client.get_by_id(99999).await;
 
         assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            BooruError::PostNotFound(99999)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_forbidden_with_api_message() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/posts.json"))
+            .respond_with(ResponseTemplate::new(403).set_body_string(
+                r#"{"success":false,"error":"User::PrivilegeError","message":"Access denied"}"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let client = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build();
+
+        let result = client.get().await;
+
+        assert!(matches!(
+            result,
+            Err(BooruError::Forbidden(ref message)) if message == "Access denied"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_with_retry_after() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/posts.json"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "12"))
+            .mount(&mock_server)
+            .await;
+
+        let client = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build();
+
+        let err = client.get().await.unwrap_err();
+
+        assert!(matches!(err, BooruError::RateLimited { .. }));
+        assert_eq!(err.retry_after(), Some(std::time::Duration::from_secs(12)));
+    }
+
+    #[tokio::test]
+    async fn test_unprocessable_api_error() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/posts.json"))
+            .respond_with(ResponseTemplate::new(422).set_body_string(
+                r#"{"success":false,"message":"You cannot search for more than 2 tags at a time"}"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let client = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build();
+
+        match client.get().await.unwrap_err() {
+            BooruError::ApiError {
+                status,
+                message,
+                body_snippet,
+            } => {
+                assert_eq!(status, 422);
+                assert_eq!(message, "You cannot search for more than 2 tags at a time");
+                assert!(body_snippet.contains("success"));
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
     }
 }
 
@@ -597,6 +761,54 @@ mod mock_gelbooru {
         assert!(posts.is_ok());
         assert!(posts.unwrap().is_empty());
     }
+
+    #[tokio::test]
+    async fn test_xml_error_page() {
+        let mock_server = MockServer::start().await;
+
+        let error_response = r#"<?xml version="1.0" encoding="UTF-8"?><response success="false" reason="Search error: API limited due to abuse."/>"#;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(error_response))
+            .mount(&mock_server)
+            .await;
+
+        let client = GelbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .set_credentials("test_key", "test_user")
+            .build();
+
+        let result = client.get().await;
+
+        assert!(matches!(
+            result,
+            Err(BooruError::ApiError { ref message, .. })
+                if message == "Search error: API limited due to abuse."
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_post_not_found_404() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .and(query_param("id", "99999"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("Not Found"))
+            .mount(&mock_server)
+            .await;
+
+        let client = GelbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .set_credentials("test_key", "test_user")
+            .build();
+
+        assert!(matches!(
+            client.get_by_id(99999).await,
+            Err(BooruError::PostNotFound(99999))
+        ));
+    }
 }
 
 #[cfg(feature = "rule34")]
@@ -746,4 +958,2874 @@ mod mock_rule34 {
         assert_eq!(post.md5(), Some("rule34hash123"));
         assert_eq!(post.source(), Some("https://pixiv.net/artworks/789"));
     }
+
+    #[tokio::test]
+    async fn test_post_not_found_404() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .and(query_param("id", "99999"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("Not Found"))
+            .mount(&mock_server)
+            .await;
+
+        let client = Rule34Client::builder()
+            .with_custom_url(&mock_server.uri())
+            .set_credentials("test_key", "test_user")
+            .build();
+
+        assert!(matches!(
+            client.get_by_id(99999).await,
+            Err(BooruError::PostNotFound(99999))
+        ));
+    }
+}
+
+mod mock_danbooru_tags {
+    use super::*;
+    use booru_rs::autocomplete::TagSuggestion;
+    use booru_rs::danbooru::{DanbooruTagCategory, DanbooruTagOrder};
+    use booru_rs::prelude::*;
+
+    fn tags_json(first_id: u32) -> String {
+        format!(
+            r#"[
+                {{
+                    "id": {first_id},
+                    "name": "cat_ears",
+                    "post_count": 250000,
+                    "category": 0,
+                    "created_at": "2013-02-28T05:10:00.000Z",
+                    "updated_at": "2024-01-15T12:00:00.000Z",
+                    "is_deprecated": false,
+                    "words": ["cat", "ears"]
+                }},
+                {{
+                    "id": {},
+                    "name": "cat_tail",
+                    "post_count": "120000",
+                    "category": 0,
+                    "created_at": "2013-02-28T05:10:00.000Z",
+                    "updated_at": "2024-01-15T12:00:00.000Z",
+                    "is_deprecated": true,
+                    "words": ["cat", "tail"]
+                }}
+            ]"#,
+            first_id + 1
+        )
+    }
+
+    #[tokio::test]
+    async fn test_tag_search_params() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/tags.json"))
+            .and(query_param("search[name_matches]", "cat_*"))
+            .and(query_param("search[category]", "0"))
+            .and(query_param("search[order]", "count"))
+            .and(query_param("search[hide_empty]", "true"))
+            .and(query_param("limit", "2"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(tags_json(1)))
+            .mount(&mock_server)
+            .await;
+
+        let tags = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .tags()
+            .name_matches("cat_*")
+            .category(DanbooruTagCategory::General)
+            .order(DanbooruTagOrder::Count)
+            .hide_empty()
+            .limit(2)
+            .get()
+            .await
+            .unwrap();
+
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].name, "cat_ears");
+        assert_eq!(tags[0].category(), Some(DanbooruTagCategory::General));
+        assert_eq!(tags[0].category_name(), "general");
+        assert_eq!(tags[1].post_count, 120000);
+        assert!(tags[1].is_deprecated);
+    }
+
+    #[tokio::test]
+    async fn test_tag_stream_walks_pages() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/tags.json"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(tags_json(1)))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/tags.json"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(tags_json(3)))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/tags.json"))
+            .and(query_param("page", "3"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .mount(&mock_server)
+            .await;
+
+        let tags = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .tags()
+            .limit(2)
+            .into_tag_stream()
+            .collect()
+            .await
+            .unwrap();
+
+        let ids: Vec<u32> = tags.iter().map(|tag| tag.id).collect();
+        assert_eq!(ids, [1, 2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_related_tags() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/related_tag.json"))
+            .and(query_param("query", "cat_ears"))
+            .and(query_param("category", "character"))
+            .and(query_param("limit", "5"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{
+                    "query": "cat_ears",
+                    "post_count": 250000,
+                    "related_tags": [
+                        {
+                            "tag": {"id": 7, "name": "hatsune_miku", "post_count": 120000, "category": 4},
+                            "cosine_similarity": 0.12,
+                            "jaccard_similarity": "0.05",
+                            "overlap_coefficient": 0.3,
+                            "frequency": 0.08
+                        }
+                    ]
+                }"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let related = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .related_tags("cat_ears")
+            .category(DanbooruTagCategory::Character)
+            .limit(5)
+            .get()
+            .await
+            .unwrap();
+
+        assert_eq!(related.len(), 1);
+        assert_eq!(related[0].tag.name, "hatsune_miku");
+        assert_eq!(related[0].category_name(), "character");
+        assert!((related[0].jaccard_similarity - 0.05).abs() < f64::EPSILON);
+
+        let suggestion = TagSuggestion::from(related[0].clone());
+        assert_eq!(suggestion.label, "hatsune miku");
+        assert_eq!(suggestion.post_count, Some(120000));
+        assert_eq!(suggestion.category, Some(4));
+    }
+}
+
+mod mock_danbooru_resolver {
+    use super::*;
+    use booru_rs::cache::Cache;
+    use booru_rs::prelude::*;
+
+    fn relation_json(antecedent: &str, consequent: &str) -> String {
+        format!(
+            r#"[{{
+                "id": 1,
+                "antecedent_name": "{antecedent}",
+                "consequent_name": "{consequent}",
+                "status": "active",
+                "reason": "",
+                "forum_topic_id": null,
+                "created_at": "2020-01-01T00:00:00.000Z",
+                "updated_at": "2020-01-01T00:00:00.000Z"
+            }}]"#
+        )
+    }
+
+    async fn mount_relations(mock_server: &MockServer) {
+        Mock::given(method("GET"))
+            .and(path("/tag_aliases.json"))
+            .and(query_param("search[antecedent_name]", "cat_girl"))
+            .and(query_param("search[status]", "active"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(relation_json("cat_girl", "cat_ears")),
+            )
+            .with_priority(1)
+            .expect(1)
+            .mount(mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/tag_implications.json"))
+            .and(query_param("search[antecedent_name]", "cat_ears"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(relation_json("cat_ears", "animal_ears")),
+            )
+            .with_priority(1)
+            .mount(mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/tag_implications.json"))
+            .and(query_param("search[antecedent_name]", "animal_ears"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(relation_json("animal_ears", "animal_focus")),
+            )
+            .with_priority(1)
+            .mount(mock_server)
+            .await;
+
+        for endpoint in ["/tag_aliases.json", "/tag_implications.json"] {
+            Mock::given(method("GET"))
+                .and(path(endpoint))
+                .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+                .mount(mock_server)
+                .await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_drops_aliases_and_implied_tags() {
+        let mock_server = MockServer::start().await;
+        mount_relations(&mock_server).await;
+
+        let resolver = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .tag_resolver();
+
+        let tags = resolver
+            .resolve(["Cat Girl", "animal_ears", "cat_ears", "rating:g", "-1boy"])
+            .await
+            .unwrap();
+
+        assert_eq!(tags, ["cat_ears", "rating:g", "-1boy"]);
+    }
+
+    #[tokio::test]
+    async fn test_expand_follows_implications() {
+        let mock_server = MockServer::start().await;
+        mount_relations(&mock_server).await;
+
+        let resolver = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .tag_resolver();
+
+        let tags = resolver.expand(["cat_girl"]).await.unwrap();
+
+        assert_eq!(tags, ["cat_ears", "animal_ears", "animal_focus"]);
+    }
+
+    #[tokio::test]
+    async fn test_resolver_uses_cache() {
+        let mock_server = MockServer::start().await;
+        mount_relations(&mock_server).await;
+
+        let resolver = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .tag_resolver()
+            .with_cache(Cache::new());
+
+        // The alias mock expects a single request.
+        assert_eq!(
+            resolver.canonical_name("cat_girl").await.unwrap(),
+            "cat_ears"
+        );
+        assert_eq!(
+            resolver.canonical_name("cat_girl").await.unwrap(),
+            "cat_ears"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_shared_cache_is_per_host() {
+        let mock_server = MockServer::start().await;
+        mount_relations(&mock_server).await;
+
+        let mirror = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/tag_aliases.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .expect(1)
+            .mount(&mirror)
+            .await;
+
+        let cache = Cache::new();
+        let resolver = |uri: &str| {
+            DanbooruClient::builder()
+                .with_custom_url(uri)
+                .build()
+                .tag_resolver()
+                .with_cache(cache.clone())
+        };
+
+        let main_name = resolver(&mock_server.uri())
+            .canonical_name("cat_girl")
+            .await
+            .unwrap();
+        let mirror_name = resolver(&mirror.uri())
+            .canonical_name("cat_girl")
+            .await
+            .unwrap();
+
+        assert_eq!(main_name, "cat_ears");
+        assert_eq!(mirror_name, "cat_girl");
+    }
+}
+
+mod mock_danbooru_pools {
+    use super::*;
+    use booru_rs::danbooru::DanbooruPoolCategory;
+    use booru_rs::download::Downloader;
+    use booru_rs::prelude::*;
+
+    fn pool_json() -> &'static str {
+        r#"{
+            "id": 42,
+            "name": "my_comic",
+            "description": "A short comic",
+            "post_ids": [30, 10, 20],
+            "post_count": 3,
+            "category": "series",
+            "is_deleted": false,
+            "created_at": "2020-01-01T00:00:00.000Z",
+            "updated_at": "2020-01-02T00:00:00.000Z"
+        }"#
+    }
+
+    fn post_json(id: u32, base: &str) -> String {
+        danbooru_post(serde_json::json!({
+            "id": id,
+            "md5": format!("md5_{id}"),
+            "file_url": format!("{base}/images/{id}.png"),
+        }))
+        .to_string()
+    }
+
+    async fn mount_pool(mock_server: &MockServer) {
+        let base = mock_server.uri();
+
+        Mock::given(method("GET"))
+            .and(path("/pools/42.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(pool_json()))
+            .mount(mock_server)
+            .await;
+
+        // Danbooru answers `id:` queries in its own order
+        let posts = format!(
+            "[{}, {}, {}]",
+            post_json(10, &base),
+            post_json(20, &base),
+            post_json(30, &base)
+        );
+        Mock::given(method("GET"))
+            .and(path("/posts.json"))
+            .and(query_param("tags", "id:30,10,20"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(posts))
+            .mount(mock_server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_get_pool() {
+        let mock_server = MockServer::start().await;
+        mount_pool(&mock_server).await;
+
+        let pool = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .pool(42)
+            .await
+            .unwrap();
+
+        assert_eq!(pool.display_name(), "my comic");
+        assert_eq!(pool.category, DanbooruPoolCategory::Series);
+        assert_eq!(pool.post_ids, [30, 10, 20]);
+    }
+
+    #[tokio::test]
+    async fn test_pool_posts_keep_pool_order() {
+        let mock_server = MockServer::start().await;
+        mount_pool(&mock_server).await;
+
+        let client = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build();
+        let pool = client.pool(42).await.unwrap();
+
+        let ids: Vec<u32> = client
+            .pool_posts(&pool)
+            .await
+            .unwrap()
+            .iter()
+            .map(|post| post.id)
+            .collect();
+
+        assert_eq!(ids, [30, 10, 20]);
+    }
+
+    #[tokio::test]
+    async fn test_download_pool_uses_sequence_filenames() {
+        let mock_server = MockServer::start().await;
+        mount_pool(&mock_server).await;
+
+        for id in [10, 20, 30] {
+            Mock::given(method("GET"))
+                .and(path(format!("/images/{id}.png")))
+                .respond_with(ResponseTemplate::new(200).set_body_string(id.to_string()))
+                .mount(&mock_server)
+                .await;
+        }
+
+        let client = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build();
+        let pool = client.pool(42).await.unwrap();
+
+        let dir = std::env::temp_dir().join("booru_rs_pool_test");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let results = Downloader::new()
+            .download_pool(&client, &pool, &dir, 2)
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(std::fs::read_to_string(dir.join("001.png")).unwrap(), "30");
+        assert_eq!(std::fs::read_to_string(dir.join("002.png")).unwrap(), "10");
+        assert_eq!(std::fs::read_to_string(dir.join("003.png")).unwrap(), "20");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_download_pool_keeps_numbers_of_missing_posts() {
+        let mock_server = MockServer::start().await;
+        let base = mock_server.uri();
+
+        Mock::given(method("GET"))
+            .and(path("/pools/42.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(pool_json()))
+            .mount(&mock_server)
+            .await;
+
+        // Post 10 was deleted
+        let posts = format!("[{}, {}]", post_json(20, &base), post_json(30, &base));
+        Mock::given(method("GET"))
+            .and(path("/posts.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(posts))
+            .mount(&mock_server)
+            .await;
+
+        for id in [20, 30] {
+            Mock::given(method("GET"))
+                .and(path(format!("/images/{id}.png")))
+                .respond_with(ResponseTemplate::new(200).set_body_string(id.to_string()))
+                .mount(&mock_server)
+                .await;
+        }
+
+        let client = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build();
+        let pool = client.pool(42).await.unwrap();
+
+        let dir = std::env::temp_dir().join("booru_rs_pool_gap_test");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let results = Downloader::new()
+            .download_pool(&client, &pool, &dir, 2)
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(std::fs::read_to_string(dir.join("001.png")).unwrap(), "30");
+        assert!(!dir.join("002.png").exists());
+        assert_eq!(std::fs::read_to_string(dir.join("003.png")).unwrap(), "20");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
+
+mod mock_danbooru_account {
+    use super::*;
+    use booru_rs::prelude::*;
+    use wiremock::matchers::basic_auth;
+
+    fn authed_client(mock_server: &MockServer) -> DanbooruClient {
+        DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .set_credentials("secret_key", "alice")
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_credentials_sent_as_basic_auth() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/posts.json"))
+            .and(basic_auth("alice", "secret_key"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(danbooru_posts_json()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let posts = authed_client(&mock_server).get().await.unwrap();
+
+        assert_eq!(posts.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_write_requires_credentials() {
+        let mock_server = MockServer::start().await;
+
+        let client = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build();
+
+        assert!(matches!(
+            client.add_favorite(1).await,
+            Err(BooruError::Unauthorized(_))
+        ));
+        assert!(matches!(
+            client.unvote(1).await,
+            Err(BooruError::Unauthorized(_))
+        ));
+        assert!(mock_server.received_requests().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_add_and_remove_favorite() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/favorites.json"))
+            .and(query_param("post_id", "7654321"))
+            .and(basic_auth("alice", "secret_key"))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .set_body_string(r#"{"id": 5, "user_id": 99, "post_id": 7654321}"#),
+            )
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("DELETE"))
+            .and(path("/favorites/7654321.json"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = authed_client(&mock_server);
+
+        let favorite = client.add_favorite(7654321).await.unwrap();
+        assert_eq!(favorite.post_id, 7654321);
+        assert_eq!(favorite.user_id, 99);
+
+        client.remove_favorite(7654321).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_list_favorites_uses_ordfav() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/posts.json"))
+            .and(query_param("tags", "ordfav:alice"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(danbooru_posts_json()))
+            .mount(&mock_server)
+            .await;
+
+        let posts = authed_client(&mock_server)
+            .favorites("alice")
+            .await
+            .unwrap();
+
+        assert_eq!(posts.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_upvote() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/posts/7654321/votes.json"))
+            .and(query_param("score", "1"))
+            .respond_with(ResponseTemplate::new(201).set_body_string(
+                r#"{"id": 1, "post_id": 7654321, "user_id": 99, "score": 1, "is_deleted": false}"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let vote = authed_client(&mock_server).upvote(7654321).await.unwrap();
+
+        assert!(vote.is_upvote());
+        assert_eq!(vote.post_id, 7654321);
+    }
+
+    #[tokio::test]
+    async fn test_downvote_forbidden() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/posts/7654321/votes.json"))
+            .and(query_param("score", "-1"))
+            .respond_with(ResponseTemplate::new(403).set_body_string(
+                r#"{"success": false, "message": "You do not have permission to downvote"}"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let result = authed_client(&mock_server).downvote(7654321).await;
+
+        assert!(matches!(result, Err(BooruError::Forbidden(_))));
+    }
+}
+
+mod mock_danbooru_counts {
+    use super::*;
+    use booru_rs::prelude::*;
+
+    #[tokio::test]
+    async fn test_count_uses_configured_tags() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/counts/posts.json"))
+            .and(query_param("tags", "cat_ears rating:general"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(r#"{"counts":{"posts":53210}}"#),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let builder = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .tag("cat_ears")
+            .unwrap()
+            .rating(DanbooruRating::General);
+
+        assert_eq!(builder.count().await.unwrap(), 53210);
+        assert_eq!(builder.build().count().await.unwrap(), 53210);
+    }
+
+    #[tokio::test]
+    async fn test_count_unavailable() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/counts/posts.json"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(r#"{"counts":{"posts":null}}"#),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let result = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .count()
+            .await;
+
+        assert!(matches!(result, Err(BooruError::EmptyResponse)));
+    }
+
+    #[tokio::test]
+    async fn test_post_stream_size_hint() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/posts.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(danbooru_posts_json()))
+            .mount(&mock_server)
+            .await;
+
+        let mut stream = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .into_post_stream()
+            .with_total(3);
+
+        assert_eq!(stream.total(), Some(3));
+        assert_eq!(stream.size_hint(), (0, Some(3)));
+
+        stream.next().await.unwrap().unwrap();
+        assert_eq!(stream.size_hint(), (0, Some(2)));
+
+        let stream = stream.max_posts(2);
+        assert_eq!(stream.size_hint(), (0, Some(1)));
+    }
+}
+
+mod mock_danbooru_wiki_artists {
+    use super::*;
+    use booru_rs::prelude::*;
+
+    #[tokio::test]
+    async fn test_wiki_page_by_title() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/wiki_pages/k%2Ek%2E_%28artist%29.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{
+                    "id": 42,
+                    "title": "k.k._(artist)",
+                    "body": "An artist.",
+                    "other_names": ["kk"],
+                    "is_locked": false,
+                    "is_deleted": false,
+                    "category_name": 1
+                }"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let page = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .wiki_page("k.k._(artist)")
+            .await
+            .unwrap();
+
+        assert_eq!(page.id, 42);
+        assert_eq!(page.display_title(), "k.k. (artist)");
+        assert_eq!(page.other_names, vec!["kk"]);
+        assert_eq!(page.extra["category_name"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_wiki_page_not_found() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/wiki_pages/nonexistent.json"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+
+        let result = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .wiki_page("nonexistent")
+            .await;
+
+        assert!(result.unwrap_err().is_not_found());
+    }
+
+    #[tokio::test]
+    async fn test_artist_by_url() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/artists.json"))
+            .and(query_param(
+                "search[url_matches]",
+                "https://www.pixiv.net/users/123456",
+            ))
+            .and(query_param("search[is_deleted]", "false"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"[{
+                    "id": 9,
+                    "name": "some_artist",
+                    "group_name": "",
+                    "other_names": ["\u3042\u30fc\u3068"],
+                    "is_banned": false,
+                    "is_deleted": false,
+                    "urls": [
+                        {"id": 1, "artist_id": 9, "url": "https://www.pixiv.net/users/123456", "is_active": true},
+                        {"id": 2, "artist_id": 9, "url": "https://old.example.com", "is_active": false}
+                    ]
+                }]"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let artists = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .artists()
+            .url_matches("https://www.pixiv.net/users/123456")
+            .deleted(false)
+            .get()
+            .await
+            .unwrap();
+
+        assert_eq!(artists.len(), 1);
+        assert_eq!(artists[0].name, "some_artist");
+        assert_eq!(artists[0].other_names, vec!["\u{3042}\u{30fc}\u{3068}"]);
+        assert_eq!(
+            artists[0].active_urls().collect::<Vec<_>>(),
+            vec!["https://www.pixiv.net/users/123456"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_artist_search_by_other_name() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/artists.json"))
+            .and(query_param("search[any_other_name_like]", "kk*"))
+            .and(query_param("limit", "5"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let artists = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .artists()
+            .other_name("kk*")
+            .limit(5)
+            .get()
+            .await
+            .unwrap();
+
+        assert!(artists.is_empty());
+    }
+}
+
+mod mock_danbooru_notes_comments {
+    use super::*;
+    use booru_rs::prelude::*;
+
+    #[tokio::test]
+    async fn test_notes_for_post() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/notes.json"))
+            .and(query_param("search[post_id]", "7654321"))
+            .and(query_param("search[is_active]", "true"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"[{
+                    "id": 1,
+                    "post_id": 7654321,
+                    "x": 120,
+                    "y": "45",
+                    "width": 200,
+                    "height": 80,
+                    "body": "<b>Hello!</b>",
+                    "is_active": true,
+                    "version": 3
+                }]"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let notes = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .notes()
+            .post_id(7654321)
+            .active(true)
+            .get()
+            .await
+            .unwrap();
+
+        assert_eq!(notes.len(), 1);
+        assert_eq!((notes[0].x, notes[0].y), (120, 45));
+        assert_eq!((notes[0].width, notes[0].height), (200, 80));
+        assert_eq!(notes[0].body, "<b>Hello!</b>");
+    }
+
+    #[tokio::test]
+    async fn test_comment_stream_walks_pages() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/comments.json"))
+            .and(query_param("group_by", "comment"))
+            .and(query_param("search[post_id]", "7654321"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"[
+                    {"id": 3, "post_id": 7654321, "creator_id": 1, "body": "Nice", "score": 2},
+                    {"id": 2, "post_id": 7654321, "creator_id": 2, "body": "Source?", "score": -1}
+                ]"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/comments.json"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"[{"id": 1, "post_id": 7654321, "creator_id": 1, "body": "First", "is_sticky": true}]"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/comments.json"))
+            .and(query_param("page", "3"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .mount(&mock_server)
+            .await;
+
+        let comments = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .comments()
+            .post_id(7654321)
+            .limit(2)
+            .into_comment_stream()
+            .collect()
+            .await
+            .unwrap();
+
+        let ids: Vec<u32> = comments.iter().map(|comment| comment.id).collect();
+        assert_eq!(ids, [3, 2, 1]);
+        assert_eq!(comments[1].score, -1);
+        assert!(comments[2].is_sticky);
+    }
+}
+
+mod mock_danbooru_post_versions {
+    use super::*;
+    use booru_rs::danbooru::DanbooruRating;
+    use booru_rs::prelude::*;
+
+    #[tokio::test]
+    async fn test_post_history_replays_versions() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/post_versions.json"))
+            .and(query_param("search[post_id]", "7654321"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"[
+                    {
+                        "id": 12, "post_id": 7654321, "version": 2,
+                        "tags": "1girl cat_ears", "added_tags": ["cat_ears"], "removed_tags": ["dog_ears"],
+                        "rating": "s", "rating_changed": true, "updater_id": 5,
+                        "updated_at": "2024-01-02T00:00:00.000-05:00"
+                    },
+                    {
+                        "id": 10, "post_id": 7654321, "version": 1,
+                        "tags": "1girl dog_ears", "added_tags": ["1girl", "dog_ears"], "removed_tags": [],
+                        "rating": "g", "rating_changed": false, "updater_id": 1,
+                        "updated_at": "2024-01-01T00:00:00.000-05:00"
+                    }
+                ]"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/post_versions.json"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .mount(&mock_server)
+            .await;
+
+        let history = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .post_history(7654321)
+            .await
+            .unwrap();
+
+        let versions: Vec<u32> = history.versions().iter().map(|v| v.version).collect();
+        assert_eq!(versions, [1, 2]);
+        assert!(history.versions()[1].rating_changed);
+        assert_eq!(history.rating_at_version(1), Some(DanbooruRating::General));
+        assert_eq!(
+            history.rating_at_version(2),
+            Some(DanbooruRating::Sensitive)
+        );
+
+        let tags: Vec<String> = history.tags_at_version(1).into_iter().collect();
+        assert_eq!(tags, ["1girl", "dog_ears"]);
+
+        let latest = history.versions()[1].updated_time().unwrap();
+        let tags: Vec<String> = history.tags_at(latest).into_iter().collect();
+        assert_eq!(tags, ["1girl", "cat_ears"]);
+    }
+
+    #[tokio::test]
+    async fn test_post_versions_by_updater() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/post_versions.json"))
+            .and(query_param("search[updater_name]", "some_user"))
+            .and(query_param("limit", "20"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let versions = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .post_versions()
+            .updater_name("some_user")
+            .limit(20)
+            .get()
+            .await
+            .unwrap();
+
+        assert!(versions.is_empty());
+    }
+}
+
+mod mock_danbooru_explore {
+    use super::*;
+    use booru_rs::danbooru::DanbooruExploreScale;
+    use booru_rs::prelude::*;
+
+    #[tokio::test]
+    async fn test_popular_with_date_and_scale() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/explore/posts/popular.json"))
+            .and(query_param("date", "2024-06-01"))
+            .and(query_param("scale", "week"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(danbooru_posts_json()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let posts = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .popular()
+            .date("2024-06-01")
+            .unwrap()
+            .scale(DanbooruExploreScale::Week)
+            .get()
+            .await
+            .unwrap();
+
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].id, 7654321);
+    }
+
+    #[tokio::test]
+    async fn test_curated_and_most_viewed() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/explore/posts/curated.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(danbooru_posts_json()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/explore/posts/viewed.json"))
+            .and(query_param("date", "2024-06-01"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build();
+
+        assert_eq!(client.curated().get().await.unwrap().len(), 1);
+        assert!(
+            client
+                .most_viewed()
+                .date("2024-06-01")
+                .unwrap()
+                .get()
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_explore_limit_and_page() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/explore/posts/popular.json"))
+            .and(query_param("limit", "5"))
+            .and(query_param("page", "3"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(danbooru_posts_json()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let posts = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .popular()
+            .limit(5)
+            .page(3)
+            .get()
+            .await
+            .unwrap();
+
+        assert_eq!(posts.len(), 1);
+    }
+
+    #[test]
+    fn test_explore_rejects_invalid_dates() {
+        let client = DanbooruClient::builder().build();
+
+        for date in [
+            "2024-6-1",
+            "01-06-2024",
+            "2024-13-01",
+            "2023-02-29",
+            "2024/06/01",
+        ] {
+            assert!(
+                matches!(
+                    client.popular().date(date),
+                    Err(BooruError::InvalidDate(ref d)) if d == date
+                ),
+                "{date} should be rejected"
+            );
+        }
+        assert!(client.popular().date("2024-02-29").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_explore_lenient_skips_bad_posts() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/explore/posts/popular.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                danbooru_post(serde_json::json!({"id": 1})),
+                {"id": "oops"}
+            ])))
+            .mount(&mock_server)
+            .await;
+
+        let posts = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .lenient()
+            .build()
+            .popular()
+            .get()
+            .await
+            .unwrap();
+
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].id, 1);
+    }
+}
+
+mod mock_danbooru_uploads {
+    use super::*;
+    use booru_rs::danbooru::{DanbooruNewPost, DanbooruRating};
+    use booru_rs::prelude::*;
+    use std::time::Duration;
+    use wiremock::matchers::{basic_auth, body_string_contains, header_regex};
+
+    fn authed_client(mock_server: &MockServer) -> DanbooruClient {
+        DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .set_credentials("secret_key", "alice")
+            .build()
+    }
+
+    fn upload_json(status: &str) -> String {
+        format!(
+            r#"{{
+                "id": 55,
+                "status": "{status}",
+                "media_asset_count": 1,
+                "upload_media_assets": [
+                    {{"id": 77, "upload_id": 55, "media_asset_id": 99, "status": "active"}}
+                ]
+            }}"#
+        )
+    }
+
+    #[tokio::test]
+    async fn test_upload_file_sends_multipart() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/uploads.json"))
+            .and(basic_auth("alice", "secret_key"))
+            .and(header_regex(
+                "content-type",
+                "^multipart/form-data; boundary=",
+            ))
+            .and(body_string_contains(
+                r#"name="upload[files][0]"; filename="booru_rs_upload.png""#,
+            ))
+            .and(body_string_contains("fake image data"))
+            .respond_with(ResponseTemplate::new(201).set_body_string(upload_json("pending")))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let file = std::env::temp_dir().join("booru_rs_upload.png");
+        std::fs::write(&file, b"fake image data").unwrap();
+
+        let upload = authed_client(&mock_server)
+            .upload_file(&file)
+            .await
+            .unwrap();
+
+        assert_eq!(upload.id, 55);
+        assert!(!upload.is_finished());
+    }
+
+    #[tokio::test]
+    async fn test_upload_source_and_wait() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/uploads.json"))
+            .and(body_string_contains(
+                "upload%5Bsource%5D=https%3A%2F%2Fexample.com%2Fart",
+            ))
+            .respond_with(ResponseTemplate::new(201).set_body_string(upload_json("pending")))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/uploads/55.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(upload_json("processing")))
+            .up_to_n_times(2)
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/uploads/55.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(upload_json("completed")))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = authed_client(&mock_server);
+        let upload = client
+            .upload_source("https://example.com/art")
+            .await
+            .unwrap();
+        let upload = client
+            .wait_for_upload(upload.id, Duration::from_millis(1))
+            .await
+            .unwrap();
+
+        assert!(upload.is_completed());
+        assert_eq!(upload.upload_media_assets[0].id, 77);
+        assert_eq!(upload.upload_media_assets[0].media_asset_id, Some(99));
+    }
+
+    #[tokio::test]
+    async fn test_create_post() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/posts.json"))
+            .and(body_string_contains("upload_media_asset_id=77"))
+            .and(body_string_contains("post%5Btag_string%5D=1girl+cat_ears"))
+            .and(body_string_contains("post%5Brating%5D=g"))
+            .and(body_string_contains("post%5Bparent_id%5D=12"))
+            .respond_with(ResponseTemplate::new(201).set_body_string(danbooru_post_json()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let post = authed_client(&mock_server)
+            .create_post(
+                &DanbooruNewPost::new(77, ["1girl", "cat_ears"])
+                    .rating(DanbooruRating::General)
+                    .parent_id(12),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(post.id, 7654321);
+    }
+
+    #[tokio::test]
+    async fn test_create_post_duplicate() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/posts.json"))
+            .respond_with(
+                ResponseTemplate::new(422)
+                    .set_body_string(r#"{"errors":{"md5":["duplicate: 4242"]}}"#),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let result = authed_client(&mock_server)
+            .create_post(&DanbooruNewPost::new(77, ["1girl"]))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(BooruError::DuplicatePost { post_id: 4242 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_upload_requires_credentials() {
+        let mock_server = MockServer::start().await;
+
+        let client = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build();
+
+        assert!(matches!(
+            client.upload_source("https://example.com/art").await,
+            Err(BooruError::Unauthorized(_))
+        ));
+    }
+}
+
+mod mock_danbooru_edit {
+    use super::*;
+    use booru_rs::danbooru::{DanbooruPost, DanbooruRating};
+    use booru_rs::prelude::*;
+    use wiremock::matchers::{basic_auth, body_string_contains};
+
+    fn post() -> DanbooruPost {
+        serde_json::from_str(danbooru_post_json()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_sends_nothing() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(wiremock::matchers::any())
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let diff = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .edit_post(&post())
+            .add_tags(["smile", "solo"])
+            .remove_tags(["blue_hair"])
+            .rating(DanbooruRating::Sensitive)
+            .source("https://pixiv.net/artworks/123456")
+            .parent_id(Some(12))
+            .dry_run();
+
+        assert_eq!(diff.tag_string, "1girl solo cat_ears smile");
+        assert_eq!(diff.added_tags, ["smile"]);
+        assert_eq!(diff.removed_tags, ["blue_hair"]);
+        assert_eq!(
+            diff.rating,
+            Some((Some(DanbooruRating::General), DanbooruRating::Sensitive))
+        );
+        assert_eq!(diff.source, None);
+        assert_eq!(diff.parent_id, Some((None, Some(12))));
+        assert!(!diff.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_edit_sends_old_tag_string() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("PUT"))
+            .and(path("/posts/7654321.json"))
+            .and(basic_auth("alice", "secret_key"))
+            .and(body_string_contains(
+                "post%5Btag_string%5D=1girl+solo+blue_hair+smile",
+            ))
+            .and(body_string_contains(
+                "post%5Bold_tag_string%5D=1girl+solo+cat_ears+blue_hair",
+            ))
+            .and(body_string_contains("post%5Brating%5D=q"))
+            .and(body_string_contains("post%5Bold_rating%5D=g"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(danbooru_post_json()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let updated = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .set_credentials("secret_key", "alice")
+            .build()
+            .edit_post(&post())
+            .add_tags(["smile"])
+            .remove_tags(["cat_ears"])
+            .rating(DanbooruRating::Questionable)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(updated.id, 7654321);
+    }
+
+    #[tokio::test]
+    async fn test_edit_requires_credentials() {
+        let mock_server = MockServer::start().await;
+
+        let result = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .edit_post(&post())
+            .add_tags(["smile"])
+            .send()
+            .await;
+
+        assert!(matches!(result, Err(BooruError::Unauthorized(_))));
+    }
+}
+
+mod mock_danbooru_saved_searches {
+    use super::*;
+    use booru_rs::danbooru::DanbooruSavedSearch;
+    use booru_rs::prelude::*;
+    use wiremock::matchers::{basic_auth, body_string_contains};
+
+    fn authed_client(mock_server: &MockServer) -> DanbooruClient {
+        DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .set_credentials("secret_key", "alice")
+            .build()
+    }
+
+    const SAVED_SEARCH_JSON: &str = r#"{
+        "id": 31,
+        "user_id": 1,
+        "query": "cat_ears rating:g",
+        "labels": ["daily", "cats"]
+    }"#;
+
+    #[tokio::test]
+    async fn test_list_saved_searches_by_label() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/saved_searches.json"))
+            .and(basic_auth("alice", "secret_key"))
+            .and(query_param("search[label]", "daily"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(format!("[{SAVED_SEARCH_JSON}]")),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let saved = authed_client(&mock_server)
+            .saved_searches()
+            .label("daily")
+            .get()
+            .await
+            .unwrap();
+
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].labels, ["daily", "cats"]);
+    }
+
+    #[tokio::test]
+    async fn test_create_update_delete() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/saved_searches.json"))
+            .and(body_string_contains(
+                "saved_search%5Bquery%5D=cat_ears+rating%3Ag",
+            ))
+            .and(body_string_contains(
+                "saved_search%5Blabel_string%5D=daily+cats",
+            ))
+            .respond_with(ResponseTemplate::new(201).set_body_string(SAVED_SEARCH_JSON))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("PUT"))
+            .and(path("/saved_searches/31.json"))
+            .and(body_string_contains("saved_search%5Bquery%5D=dog_ears"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("DELETE"))
+            .and(path("/saved_searches/31.json"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = authed_client(&mock_server);
+
+        let saved = client
+            .create_saved_search("cat_ears rating:g", ["daily", "cats"])
+            .await
+            .unwrap();
+        assert_eq!(saved.id, 31);
+
+        client
+            .update_saved_search(saved.id, "dog_ears", ["daily"])
+            .await
+            .unwrap();
+        client.delete_saved_search(saved.id).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_saved_search_drives_post_query() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/posts.json"))
+            .and(query_param("tags", "cat_ears rating:g"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(danbooru_posts_json()))
+            .mount(&mock_server)
+            .await;
+
+        let client = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .tag("solo")
+            .unwrap()
+            .build();
+        let saved = serde_json::from_str(SAVED_SEARCH_JSON).unwrap();
+
+        let posts = client
+            .saved_search_builder(&saved)
+            .build()
+            .get()
+            .await
+            .unwrap();
+        assert_eq!(posts.len(), 1);
+
+        let mut stream = client.saved_search_stream(&saved);
+        assert_eq!(stream.next().await.unwrap().unwrap().id, 7654321);
+    }
+
+    #[tokio::test]
+    async fn test_saved_search_ignores_tag_limit() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/posts.json"))
+            .and(query_param("tags", "cat_ears rating:g order:score"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(danbooru_posts_json()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build();
+        let mut saved: DanbooruSavedSearch = serde_json::from_str(SAVED_SEARCH_JSON).unwrap();
+        saved.query = "cat_ears rating:g order:score".to_string();
+
+        let posts = client
+            .saved_search_builder(&saved)
+            .build()
+            .get()
+            .await
+            .unwrap();
+        assert_eq!(posts.len(), 1);
+    }
+}
+
+mod mock_danbooru_media_assets {
+    use super::*;
+    use booru_rs::danbooru::DanbooruPost;
+    use booru_rs::download::{DownloadOptions, Downloader};
+    use booru_rs::model::{MediaType, Post};
+
+    fn post_with_variants(base: &str, file_ext: &str, variants: &str) -> DanbooruPost {
+        let fields = serde_json::from_str(&format!(
+            r#"{{
+                "id": 101,
+                "md5": "abc",
+                "file_ext": "{file_ext}",
+                "file_url": "{base}/original/abc.{file_ext}",
+                "large_file_url": null,
+                "preview_file_url": null,
+                "image_width": 2000,
+                "image_height": 1500,
+                "media_asset": {{
+                    "id": 5,
+                    "md5": "abc",
+                    "file_ext": "{file_ext}",
+                    "image_width": 2000,
+                    "image_height": 1500,
+                    "duration": "4.5",
+                    "pixel_hash": "def",
+                    "variants": {variants}
+                }}
+            }}"#
+        ))
+        .unwrap();
+
+        serde_json::from_value(danbooru_post(fields)).unwrap()
+    }
+
+    fn image_variants(base: &str) -> String {
+        format!(
+            r#"[
+                {{"type": "180x180", "url": "{base}/180/abc.jpg", "width": 180, "height": 135, "file_ext": "jpg"}},
+                {{"type": "360x360", "url": "{base}/360/abc.jpg", "width": 360, "height": 270, "file_ext": "jpg"}},
+                {{"type": "720x720", "url": "{base}/720/abc.webp", "width": 720, "height": 540, "file_ext": "webp"}},
+                {{"type": "sample", "url": "{base}/sample/abc.jpg", "width": 850, "height": 638, "file_ext": "jpg"}},
+                {{"type": "original", "url": "{base}/original/abc.png", "width": 2000, "height": 1500, "file_ext": "png"}}
+            ]"#
+        )
+    }
+
+    #[test]
+    fn test_best_variant_for_size() {
+        let post = post_with_variants("https://cdn", "png", &image_variants("https://cdn"));
+        let asset = post.media_asset.as_ref().unwrap();
+
+        assert_eq!(asset.variants.len(), 5);
+        assert_eq!(asset.duration, Some(4.5));
+        assert_eq!(asset.variant("sample").unwrap().width, 850);
+        assert_eq!(post.media_type(), MediaType::Image);
+
+        assert_eq!(
+            post.file_url_for_size(150, 150),
+            Some("https://cdn/180/abc.jpg")
+        );
+        assert_eq!(
+            post.file_url_for_size(500, 500),
+            Some("https://cdn/720/abc.webp")
+        );
+        assert_eq!(
+            post.file_url_for_size(4000, 4000),
+            Some("https://cdn/original/abc.png")
+        );
+    }
+
+    #[test]
+    fn test_animated_posts_keep_animated_variants() {
+        let variants = r#"[
+            {"type": "180x180", "url": "https://cdn/180/abc.jpg", "width": 180, "height": 135, "file_ext": "jpg"},
+            {"type": "sample", "url": "https://cdn/sample/abc.webm", "width": 850, "height": 638, "file_ext": "webm"},
+            {"type": "original", "url": "https://cdn/original/abc.zip", "width": 2000, "height": 1500, "file_ext": "zip"}
+        ]"#;
+        let post = post_with_variants("https://cdn", "zip", variants);
+
+        assert_eq!(post.media_type(), MediaType::Ugoira);
+        assert!(post.media_type().is_animated());
+        assert_eq!(
+            post.file_url_for_size(100, 100),
+            Some("https://cdn/sample/abc.webm")
+        );
+    }
+
+    #[test]
+    fn test_posts_without_media_asset_use_file_url() {
+        let post: DanbooruPost = serde_json::from_str(danbooru_post_json()).unwrap();
+
+        assert!(post.media_asset.is_none());
+        assert_eq!(post.file_url_for_size(180, 180), post.file_url());
+    }
+
+    #[tokio::test]
+    async fn test_download_with_target_size() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/720/abc.webp"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"webp".to_vec()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let post = post_with_variants(
+            &mock_server.uri(),
+            "png",
+            &image_variants(&mock_server.uri()),
+        );
+        let dest = std::env::temp_dir().join("booru_rs_variant_test");
+        let _ = std::fs::remove_dir_all(&dest);
+
+        let result = Downloader::new()
+            .options(DownloadOptions::default().target_size(600, 600))
+            .download_post(&post, &dest)
+            .await
+            .unwrap();
+
+        assert_eq!(result.path, dest.join("101.webp"));
+        assert_eq!(result.size, 4);
+    }
+}
+
+mod mock_get_by_ids {
+    use super::*;
+    use booru_rs::prelude::*;
+    use wiremock::matchers::query_param_is_missing;
+
+    #[tokio::test]
+    async fn test_danbooru_get_by_ids() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/posts.json"))
+            .and(query_param("tags", "id:3,1,2"))
+            .and(query_param("limit", "3"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                danbooru_post(serde_json::json!({"id": 1})),
+                danbooru_post(serde_json::json!({"id": 3})),
+            ])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let result = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .tag("cat_ears")
+            .unwrap()
+            .build()
+            .get_by_ids(&[3, 1, 2, 1])
+            .await
+            .unwrap();
+
+        let ids: Vec<u32> = result.posts.iter().map(|post| post.id).collect();
+        assert_eq!(ids, [3, 1]);
+        assert_eq!(result.missing, [2]);
+        assert!(!result.is_complete());
+    }
+
+    #[tokio::test]
+    async fn test_danbooru_get_by_ids_in_chunks() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/posts.json"))
+            .and(query_param("limit", "200"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                danbooru_post(serde_json::json!({"id": 1}),)
+            ])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/posts.json"))
+            .and(query_param("limit", "50"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                danbooru_post(serde_json::json!({"id": 250}),)
+            ])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let ids: Vec<u32> = (1..=250).collect();
+        let result = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .get_by_ids(&ids)
+            .await
+            .unwrap();
+
+        assert_eq!(result.posts.len(), 2);
+        assert_eq!(result.posts[1].id, 250);
+        assert_eq!(result.missing.len(), 248);
+    }
+
+    #[tokio::test]
+    async fn test_gelbooru_get_by_ids() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .and(query_param("tags", "{id:6 ~ id:5}"))
+            .and(query_param("limit", "2"))
+            .and(query_param_is_missing("id"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "post": [
+                    gelbooru_post(serde_json::json!({"id": 5})),
+                    gelbooru_post(serde_json::json!({"id": 6})),
+                ]
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let result = GelbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .set_credentials("test_key", "test_user")
+            .build()
+            .get_by_ids(&[6, 5])
+            .await
+            .unwrap();
+
+        let ids: Vec<u32> = result.posts.iter().map(|post| post.id).collect();
+        assert_eq!(ids, [6, 5]);
+        assert!(result.is_complete());
+    }
+
+    #[tokio::test]
+    async fn test_gelbooru_get_by_ids_none_found() {
+        let mock_server = MockServer::start().await;
+
+        // Gelbooru leaves out the post list when nothing matches
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"{"@attributes": {"limit": 100, "offset": 0, "count": 0}}"#),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let result = GelbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .set_credentials("test_key", "test_user")
+            .build()
+            .get_by_ids(&[7, 8])
+            .await
+            .unwrap();
+
+        assert!(result.posts.is_empty());
+        assert_eq!(result.missing, [7, 8]);
+    }
+}
+
+mod mock_md5_lookup {
+    use super::*;
+    use booru_rs::lookup::{Md5Lookup, Site};
+    use booru_rs::prelude::*;
+
+    const MD5: &str = "0123456789abcdef0123456789abcdef";
+
+    #[tokio::test]
+    async fn test_danbooru_get_by_md5() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/posts.json"))
+            .and(query_param("tags", format!("md5:{MD5}")))
+            .and(query_param("limit", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(danbooru_posts_json()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let post = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .get_by_md5(&MD5.to_uppercase())
+            .await
+            .unwrap();
+
+        assert_eq!(post.unwrap().id, 7654321);
+    }
+
+    #[tokio::test]
+    async fn test_get_by_md5_not_found() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .mount(&mock_server)
+            .await;
+
+        let post = SafebooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .get_by_md5(MD5)
+            .await
+            .unwrap();
+
+        assert!(post.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_by_md5_rejects_invalid_hash() {
+        let client = SafebooruClient::builder()
+            .with_custom_url("http://127.0.0.1:9")
+            .build();
+
+        let result = client.get_by_md5("not-a-hash").await;
+
+        assert!(matches!(result, Err(BooruError::InvalidTag { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_lookup_across_sites() {
+        let danbooru = MockServer::start().await;
+        let safebooru = MockServer::start().await;
+        let gelbooru = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/posts.json"))
+            .and(query_param("tags", format!("md5:{MD5}")))
+            .respond_with(ResponseTemplate::new(200).set_body_string(danbooru_posts_json()))
+            .mount(&danbooru)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .and(query_param("tags", format!("md5:{MD5}")))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .mount(&safebooru)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&gelbooru)
+            .await;
+
+        let result = Md5Lookup::new()
+            .danbooru(DanbooruClient::builder().with_custom_url(&danbooru.uri()))
+            .safebooru(SafebooruClient::builder().with_custom_url(&safebooru.uri()))
+            .gelbooru(GelbooruClient::builder().with_custom_url(&gelbooru.uri()))
+            .skip(Site::Rule34)
+            .find(MD5)
+            .await
+            .unwrap();
+
+        assert_eq!(result.matches.len(), 1);
+        let found = result.on(Site::Danbooru).unwrap();
+        assert_eq!(found.post_id, 7654321);
+        assert_eq!(
+            found.source.as_deref(),
+            Some("https://pixiv.net/artworks/123456")
+        );
+        assert!(result.on(Site::Safebooru).is_none());
+
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].0, Site::Gelbooru);
+        assert!(matches!(result.errors[0].1, BooruError::Unauthorized(_)));
+    }
+}
+
+mod mock_random {
+    use super::*;
+    use booru_rs::prelude::*;
+    use std::collections::HashSet;
+    use wiremock::matchers::query_param_is_missing;
+
+    #[tokio::test]
+    async fn test_danbooru_random_skips_seen_posts() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/posts/random.json"))
+            .and(query_param("tags", "cat_ears"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(danbooru_post(serde_json::json!({"id": 42}))),
+            )
+            .expect(12)
+            .mount(&mock_server)
+            .await;
+
+        // Sort tags are left out of the random search
+        let client = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .tag("cat_ears")
+            .unwrap()
+            .sort(Sort::Score)
+            .build();
+
+        let first = client.random(2).await.unwrap();
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].id, 42);
+
+        // The only matching post was already returned
+        assert!(client.random(1).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_random_history_is_shared_with_builder_clones() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/posts/random.json"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(danbooru_post(serde_json::json!({"id": 42}))),
+            )
+            .expect(5)
+            .mount(&mock_server)
+            .await;
+
+        let builder = DanbooruClient::builder().with_custom_url(&mock_server.uri());
+        let copy = builder.clone();
+
+        assert_eq!(builder.build().random(1).await.unwrap().len(), 1);
+        assert!(copy.build().random(1).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_danbooru_random_no_matches() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/posts/random.json"))
+            .respond_with(
+                ResponseTemplate::new(404).set_body_string(
+                    r#"{"success": false, "message": "That record was not found."}"#,
+                ),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let posts = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .random(5)
+            .await
+            .unwrap();
+
+        assert!(posts.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_safebooru_random_by_offset() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .and(query_param("tags", "landscape"))
+            .and(query_param_is_missing("json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<?xml version="1.0" encoding="UTF-8"?><posts count="3" offset="0"><post id="10"/></posts>"#,
+            ))
+            .mount(&mock_server)
+            .await;
+        for offset in 0..3 {
+            Mock::given(method("GET"))
+                .and(path("/index.php"))
+                .and(query_param("tags", "landscape"))
+                .and(query_param("json", "1"))
+                .and(query_param("limit", "1"))
+                .and(query_param("pid", offset.to_string()))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                    safebooru_post(serde_json::json!({"id": 10 + offset})),
+                ])))
+                .mount(&mock_server)
+                .await;
+        }
+
+        let client = SafebooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .tag("landscape")
+            .unwrap()
+            .sort(Sort::Score)
+            .build();
+
+        let first = client.random(2).await.unwrap();
+        let rest = client.random(5).await.unwrap();
+
+        assert_eq!(first.len(), 2);
+        assert_eq!(rest.len(), 1);
+        let ids: HashSet<u32> = first.iter().chain(&rest).map(|post| post.id).collect();
+        assert_eq!(ids, HashSet::from([10, 11, 12]));
+
+        assert!(client.random(1).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_gelbooru_random_error_page() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<?xml version="1.0" encoding="UTF-8"?><response success="false" reason="Search error: API limited due to abuse."/>"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let result = GelbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .set_credentials("test_key", "test_user")
+            .build()
+            .random(1)
+            .await;
+
+        assert!(matches!(result, Err(BooruError::ApiError { .. })));
+    }
+}
+
+mod mock_gelbooru_tags {
+    use super::*;
+    use booru_rs::gelbooru::{GelbooruPost, GelbooruTagOrder, GelbooruTagType};
+    use booru_rs::prelude::*;
+
+    fn client(mock_server: &MockServer) -> GelbooruClient {
+        GelbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .set_credentials("test_key", "test_user")
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_tag_search() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .and(query_param("s", "tag"))
+            .and(query_param("name_pattern", "cat%"))
+            .and(query_param("orderby", "count"))
+            .and(query_param("order", "DESC"))
+            .and(query_param("limit", "2"))
+            .and(query_param("api_key", "test_key"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{
+                    "@attributes": {"limit": 2, "offset": 0, "count": 1204},
+                    "tag": [
+                        {"id": 151, "name": "cat_ears", "count": 250000, "type": 0, "ambiguous": 0},
+                        {"id": 9001, "name": "cat_(series)", "count": "120", "type": 3, "ambiguous": 1}
+                    ]
+                }"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let tags = client(&mock_server)
+            .tags()
+            .name_pattern("cat%")
+            .order(GelbooruTagOrder::Count)
+            .limit(2)
+            .get()
+            .await
+            .unwrap();
+
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].name, "cat_ears");
+        assert_eq!(tags[0].tag_type(), Some(GelbooruTagType::General));
+        assert_eq!(tags[1].count, 120);
+        assert_eq!(tags[1].tag_type(), Some(GelbooruTagType::Copyright));
+        assert!(tags[1].ambiguous);
+    }
+
+    #[tokio::test]
+    async fn test_tag_search_no_results() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .and(query_param("s", "tag"))
+            .and(query_param("id", "5"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"{"@attributes": {"limit": 100, "offset": 0, "count": 0}}"#),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let tags = client(&mock_server).tags().id(5).get().await.unwrap();
+
+        assert!(tags.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_group_post_tags() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .and(query_param("s", "tag"))
+            .and(query_param(
+                "names",
+                "1girl artist_name cat_ears hatsune_miku vocaloid",
+            ))
+            .and(query_param("limit", "5"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"tag": [
+                    {"id": 1, "name": "1girl", "count": 1, "type": 0},
+                    {"id": 2, "name": "artist_name", "count": 1, "type": 1},
+                    {"id": 3, "name": "hatsune_miku", "count": 1, "type": 4},
+                    {"id": 4, "name": "vocaloid", "count": 1, "type": 3}
+                ]}"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let post: GelbooruPost = serde_json::from_value(gelbooru_post(serde_json::json!({
+            "id": 1,
+            "tags": "1girl artist_name hatsune_miku vocaloid cat_ears",
+        })))
+        .unwrap();
+
+        let client = client(&mock_server);
+        let types = client
+            .tag_types(post.tags.split_whitespace())
+            .await
+            .unwrap();
+        let groups = post.group_tags(&types);
+
+        assert_eq!(types.len(), 4);
+        assert_eq!(groups.artist, ["artist_name"]);
+        assert_eq!(groups.character, ["hatsune_miku"]);
+        assert_eq!(groups.copyright, ["vocaloid"]);
+        // cat_ears is unknown to the mock and counts as general
+        assert_eq!(groups.general, ["1girl", "cat_ears"]);
+        assert!(groups.metadata.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tag_types_unescapes_names() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .and(query_param("s", "tag"))
+            .and(query_param("names", "jack-o'_challenge tom_&_jerry"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"tag": [
+                    {"id": 1, "name": "jack-o&#039;_challenge", "count": 1, "type": 3},
+                    {"id": 2, "name": "tom_&amp;_jerry", "count": 1, "type": 3}
+                ]}"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let types = client(&mock_server)
+            .tag_types(["jack-o'_challenge", "tom_&_jerry"])
+            .await
+            .unwrap();
+
+        assert_eq!(types.len(), 2);
+        assert_eq!(
+            types.get("jack-o'_challenge"),
+            Some(&GelbooruTagType::Copyright)
+        );
+        assert_eq!(types.get("tom_&_jerry"), Some(&GelbooruTagType::Copyright));
+    }
+}
+
+mod mock_dapi_comments {
+    use super::*;
+    use booru_rs::prelude::*;
+
+    const COMMENTS_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <comments type="array">
+            <comment created_at="2024-05-01 12:00" post_id="1234" body="Great &amp; colorful" creator="alice" id="77" creator_id="5"/>
+            <comment created_at="2024-05-02 08:30" post_id="1234" body="Agreed" creator="bob" id="78" creator_id="6"/>
+        </comments>"#;
+
+    #[tokio::test]
+    async fn test_gelbooru_comments() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .and(query_param("s", "comment"))
+            .and(query_param("q", "index"))
+            .and(query_param("post_id", "1234"))
+            .and(query_param("api_key", "test_key"))
+            .and(query_param("user_id", "test_user"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(COMMENTS_XML))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let comments = GelbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .set_credentials("test_key", "test_user")
+            .build()
+            .comments(1234)
+            .await
+            .unwrap();
+
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0].id, 77);
+        assert_eq!(comments[0].post_id, 1234);
+        assert_eq!(comments[0].body, "Great & colorful");
+        assert_eq!(comments[0].creator, "alice");
+        assert_eq!(comments[1].creator_id, Some(6));
+    }
+
+    #[tokio::test]
+    async fn test_gelbooru_comments_error_response() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .and(query_param("s", "comment"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<?xml version="1.0" encoding="UTF-8"?><response success="false" reason="Search error"/>"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let result = GelbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .comments(1234)
+            .await;
+
+        assert!(matches!(result, Err(BooruError::ApiError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_rule34_comments_no_comments() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .and(query_param("s", "comment"))
+            .and(query_param("post_id", "42"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(r#"<comments type="array"></comments>"#),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let comments = Rule34Client::builder()
+            .with_custom_url(&mock_server.uri())
+            .set_credentials("test_key", "test_user")
+            .build()
+            .comments(42)
+            .await
+            .unwrap();
+
+        assert!(comments.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rule34_comments_missing_authentication() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .and(query_param("s", "comment"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "Missing authentication. Go to api.rule34.xxx for more information",
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let result = Rule34Client::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .comments(42)
+            .await;
+
+        match result {
+            Err(BooruError::Unauthorized(message)) => assert!(message.contains("set_credentials")),
+            other => panic!("expected Unauthorized, got {other:?}"),
+        }
+    }
+}
+
+mod mock_post_page {
+    use super::*;
+    use booru_rs::prelude::*;
+    use wiremock::matchers::query_param_is_missing;
+
+    fn rule34_json(ids: &[u32]) -> String {
+        let posts: Vec<serde_json::Value> = ids
+            .iter()
+            .map(|id| {
+                rule34_post(serde_json::json!({
+                    "id": id,
+                    "hash": format!("abc{id}"),
+                    "owner": "someone",
+                    "comment_count": 2,
+                }))
+            })
+            .collect();
+
+        serde_json::Value::from(posts).to_string()
+    }
+
+    /// Mounts the XML post list that Gelbooru-based sites report totals in.
+    async fn mount_xml_count(mock_server: &MockServer, count: u64, expected: u64) {
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .and(query_param("s", "post"))
+            .and(query_param_is_missing("json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?><posts count="{count}" offset="0"><post id="1"/></posts>"#
+            )))
+            .expect(expected)
+            .mount(mock_server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_gelbooru_get_page() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .and(query_param("s", "post"))
+            .and(query_param("pid", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "@attributes": {"limit": 2, "offset": 2, "count": 3},
+                "post": [gelbooru_post(serde_json::json!({"id": 30}))]
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let page = GelbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .set_credentials("test_key", "test_user")
+            .limit(2)
+            .page(1)
+            .build()
+            .get_page()
+            .await
+            .unwrap();
+
+        assert_eq!(page.posts.len(), 1);
+        assert_eq!(page.total, Some(3));
+        assert_eq!(page.offset, Some(2));
+        assert!(page.is_last());
+        assert_eq!(page.remaining(), Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_gelbooru_get_page_without_attributes() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "post": [gelbooru_post(serde_json::json!({"id": 30}))]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let page = GelbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .get_page()
+            .await
+            .unwrap();
+
+        assert_eq!(page.total, None);
+        assert!(!page.is_last());
+    }
+
+    #[tokio::test]
+    async fn test_safebooru_get_page() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .and(query_param("s", "post"))
+            .and(query_param("tags", "landscape"))
+            .and(query_param("pid", "2"))
+            .and(query_param("json", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                safebooru_post(serde_json::json!({"id": 42, "directory": 12, "image": "a.jpg"})),
+            ])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        mount_xml_count(&mock_server, 5012, 1).await;
+
+        let page = SafebooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .tag("landscape")
+            .unwrap()
+            .limit(10)
+            .page(2)
+            .build()
+            .get_page()
+            .await
+            .unwrap();
+
+        assert_eq!(page.total, Some(5012));
+        assert_eq!(page.offset, Some(20));
+        assert!(!page.is_last());
+
+        // Fields only the JSON API has are kept
+        let post = &page.posts[0];
+        assert_eq!(post.id, 42);
+        assert_eq!(post.directory, 12);
+        assert_eq!(post.image, "a.jpg");
+    }
+
+    #[tokio::test]
+    async fn test_rule34_post_stream_stops_at_total() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .and(query_param("pid", "0"))
+            .and(query_param("api_key", "test_key"))
+            .and(query_param("json", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(rule34_json(&[1, 2])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .and(query_param("pid", "1"))
+            .and(query_param("json", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(rule34_json(&[3])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        // The total is only fetched for the first page
+        mount_xml_count(&mock_server, 3, 1).await;
+
+        // A request for a third page would fail on the mock server
+        let mut stream = Rule34Client::builder()
+            .with_custom_url(&mock_server.uri())
+            .set_credentials("test_key", "test_user")
+            .limit(2)
+            .into_post_stream();
+
+        let first = stream.next().await.unwrap().unwrap();
+        assert_eq!(first.id, 1);
+        assert_eq!(first.hash, "abc1");
+        assert_eq!(first.owner, "someone");
+        assert_eq!(first.comment_count, 2);
+        assert_eq!(stream.total(), Some(3));
+        assert_eq!(stream.size_hint(), (1, Some(2)));
+
+        let rest = stream.collect().await.unwrap();
+        let ids: Vec<u32> = rest.iter().map(|post| post.id).collect();
+        assert_eq!(ids, [2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_post_stream_ends_at_empty_page_before_total() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .and(query_param("pid", "0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "@attributes": {"limit": 2, "offset": 0, "count": 5},
+                "post": [
+                    gelbooru_post(serde_json::json!({"id": 1})),
+                    gelbooru_post(serde_json::json!({"id": 2})),
+                ]
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        // The total says more posts follow, but the page is empty
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .and(query_param("pid", "1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"{"@attributes": {"limit": 2, "offset": 2, "count": 5}}"#),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .and(query_param("pid", "2"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let mut pages = GelbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .limit(2)
+            .into_page_stream();
+
+        assert_eq!(pages.next().await.unwrap().unwrap().len(), 2);
+        assert_eq!(pages.remaining(), Some(3));
+        assert!(pages.next().await.unwrap().unwrap().is_empty());
+        assert!(pages.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rule34_get_page_missing_authentication() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "Missing authentication. Go to api.rule34.xxx for more information",
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let result = Rule34Client::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .get_page()
+            .await;
+
+        assert!(matches!(result, Err(BooruError::Unauthorized(_))));
+    }
+
+    #[tokio::test]
+    async fn test_danbooru_get_page_has_no_total() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/posts.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(danbooru_posts_json()))
+            .mount(&mock_server)
+            .await;
+
+        let page = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build()
+            .get_page()
+            .await
+            .unwrap();
+
+        assert!(!page.posts.is_empty());
+        assert_eq!(page.total, None);
+        assert_eq!(page.offset, None);
+        assert!(!page.is_last());
+    }
+}
+
+mod mock_lenient {
+    use super::*;
+    use booru_rs::prelude::*;
+
+    /// A page where the first post has drifted field types and an
+    /// unmodelled field, the second an unknown rating and the fourth no
+    /// `tag_string`.
+    fn drifted_posts_json() -> serde_json::Value {
+        let mut missing_field = danbooru_post(serde_json::json!({"id": 4}));
+        missing_field.as_object_mut().unwrap().remove("tag_string");
+
+        serde_json::json!([
+            danbooru_post(serde_json::json!({
+                "id": 1,
+                "pixiv_id": "123456",
+                "file_size": null,
+                "score": "12",
+                "is_deleted": 0,
+                "vote_summary": {"id": 99}
+            })),
+            danbooru_post(serde_json::json!({"id": 2, "rating": "z"})),
+            danbooru_post(serde_json::json!({"id": 3, "rating": "s"})),
+            missing_field,
+        ])
+    }
+
+    async fn mount(mock_server: &MockServer) {
+        Mock::given(method("GET"))
+            .and(path("/posts.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(drifted_posts_json()))
+            .mount(mock_server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_strict_get_fails_whole_page() {
+        let mock_server = MockServer::start().await;
+        mount(&mock_server).await;
+
+        let client = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build();
+
+        assert!(matches!(client.get().await, Err(BooruError::Request(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_lenient_reports_item_errors() {
+        let mock_server = MockServer::start().await;
+        mount(&mock_server).await;
+
+        let client = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .build();
+
+        let page = client.get_lenient().await.unwrap();
+
+        assert_eq!(page.posts.len(), 2);
+        assert_eq!(page.errors.len(), 2);
+        assert_eq!(page.errors[0].index, 1);
+        assert_eq!(page.errors[0].id, Some(2));
+        assert_eq!(page.errors[1].id, Some(4));
+        assert!(page.errors[1].message.contains("tag_string"));
+
+        let post = &page.posts[0];
+        assert_eq!(post.pixiv_id, Some(123456));
+        assert_eq!(post.file_size, 0);
+        assert_eq!(post.score, 12);
+        assert!(!post.is_deleted);
+        assert_eq!(post.extra["vote_summary"]["id"], 99);
+    }
+
+    #[tokio::test]
+    async fn test_lenient_builder_skips_malformed_posts() {
+        let mock_server = MockServer::start().await;
+        mount(&mock_server).await;
+
+        let posts = DanbooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .lenient()
+            .build()
+            .get()
+            .await
+            .unwrap();
+
+        let ids: Vec<u32> = posts.iter().map(|post| post.id).collect();
+        assert_eq!(ids, [1, 3]);
+    }
+}
+
+mod mock_rate_limit {
+    use super::*;
+    use booru_rs::prelude::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_adaptive_limiter_reacts_to_429() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .respond_with(
+                ResponseTemplate::new(429)
+                    .insert_header("Retry-After", "30")
+                    .set_body_string("Too Many Requests"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let limiter = RateLimiter::adaptive(10, Duration::from_secs(1));
+
+        let client = SafebooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .rate_limiter(limiter.clone())
+            .build();
+
+        let result = client.get().await;
+        assert!(result.is_err());
+
+        let status = limiter.status().await;
+        assert!(status.throttled);
+        assert_eq!(status.current_requests, 5.0);
+        assert!(status.paused_for.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_limiter_syncs_remaining_tokens() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("X-RateLimit-Remaining", "2")
+                    .set_body_string(safebooru_posts_json()),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let limiter = RateLimiter::adaptive(10, Duration::from_secs(60));
+
+        let client = SafebooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .rate_limiter(limiter.clone())
+            .build();
+
+        let posts = client.get().await.unwrap();
+        assert_eq!(posts.len(), 2);
+        assert_eq!(limiter.available().await, 2);
+    }
+}
+
+mod mock_circuit_breaker {
+    use super::*;
+    use booru_rs::circuit::{CircuitBreaker, CircuitBreakerConfig, CircuitState};
+    use booru_rs::prelude::*;
+    use std::time::Duration;
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker::with_config(CircuitBreakerConfig {
+            minimum_requests: 2,
+            window_size: 2,
+            cool_down: Duration::from_secs(60),
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_circuit_opens_after_server_errors() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/index.php"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let breaker = breaker();
+        let client = SafebooruClient::builder()
+            .with_custom_url(&mock_server.uri())
+            .circuit_breaker(breaker.clone())
+            .build();
+
+        assert!(client.get().await.is_err());
+        assert!(client.get().await.is_err());
+
+        let host = mock_server.address().to_string();
+        assert_eq!(breaker.state(&host), CircuitState::Open);
+
+        // The third request never reaches the server
+        let result = client.get().await;
+        assert!(matches!(
+            result,
+            Err(BooruError::CircuitOpen { host: ref h, .. }) if *h == host
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_downloader_respects_open_circuit() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let dir = std::env::temp_dir().join("booru_rs_circuit_test");
+        let downloader = Downloader::new().with_circuit_breaker(breaker());
+        let url = format!("{}/image.png", mock_server.uri());
+
+        for _ in 0..2 {
+            let result = downloader.download_url(&url, &dir, None).await;
+            assert!(matches!(result, Err(BooruError::Request(_))));
+        }
+
+        let result = downloader.download_url(&url, &dir, None).await;
+        assert!(matches!(result, Err(BooruError::CircuitOpen { .. })));
+    }
 }